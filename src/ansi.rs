@@ -0,0 +1,414 @@
+use unicode_width::UnicodeWidthChar;
+
+const RESET: &str = "\x1b[0m";
+
+/// Part of a string, either an escape sequence or a visible character
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Piece<'a> {
+    Escape(&'a str),
+    Char(char),
+}
+
+/// Length in bytes of the escape sequence at the start of `s`
+fn escape_len(s: &str) -> Option<usize> {
+    let bytes = s.as_bytes();
+    if bytes.first() != Some(&0x1b) {
+        return None;
+    }
+
+    match bytes.get(1) {
+        // CSI, parameters and intermediates then a final byte
+        Some(b'[') => {
+            let end = bytes[2..].iter().position(|b| (0x40..=0x7e).contains(b));
+            Some(end.map_or(bytes.len(), |end| end + 3))
+        }
+        // OSC, ended by BEL or ESC \
+        Some(b']') => {
+            let mut i = 2;
+            while i < bytes.len() {
+                match bytes[i] {
+                    0x07 => return Some(i + 1),
+                    0x1b if bytes.get(i + 1) == Some(&b'\\') => return Some(i + 2),
+                    _ => i += 1,
+                }
+            }
+            Some(bytes.len())
+        }
+        Some(b) if b.is_ascii() => Some(2),
+        _ => Some(1),
+    }
+}
+
+/// Split `s` into escape sequences and characters
+pub fn pieces(s: &str) -> Vec<Piece<'_>> {
+    let mut out = vec![];
+    let mut rest = s;
+
+    while let Some(ch) = rest.chars().next() {
+        match escape_len(rest) {
+            Some(len) => {
+                out.push(Piece::Escape(&rest[..len]));
+                rest = &rest[len..];
+            }
+            None => {
+                out.push(Piece::Char(ch));
+                rest = &rest[ch.len_utf8()..];
+            }
+        }
+    }
+
+    out
+}
+
+/// `s` without any escape sequence
+pub fn strip(s: &str) -> String {
+    pieces(s)
+        .into_iter()
+        .filter_map(|piece| match piece {
+            Piece::Char(ch) => Some(ch),
+            Piece::Escape(_) => None,
+        })
+        .collect()
+}
+
+fn char_width(ch: char) -> usize {
+    ch.width().unwrap_or(0)
+}
+
+/// Columns `s` takes on the terminal
+pub fn width(s: &str) -> usize {
+    pieces(s)
+        .into_iter()
+        .map(|piece| match piece {
+            Piece::Char(ch) => char_width(ch),
+            Piece::Escape(_) => 0,
+        })
+        .sum()
+}
+
+fn is_sgr(escape: &str) -> bool {
+    escape.starts_with("\x1b[") && escape.ends_with('m')
+}
+
+/// `s` cut to `max` columns, ending with `…` when something was cut
+pub fn truncate(s: &str, max: usize) -> String {
+    truncate_with(s, max, "…")
+}
+
+/// `s` cut to `max` columns, ending with `ellipsis` when something was cut.
+/// Styles still open at the cut are reset.
+pub fn truncate_with(s: &str, max: usize, ellipsis: &str) -> String {
+    if width(s) <= max {
+        return String::from(s);
+    }
+
+    let target = max.saturating_sub(width(ellipsis));
+    let mut out = String::new();
+    let mut used = 0;
+    let mut styled = false;
+
+    for piece in pieces(s) {
+        match piece {
+            Piece::Escape(escape) => {
+                styled |= is_sgr(escape);
+                out.push_str(escape);
+            }
+            Piece::Char(ch) => {
+                let w = char_width(ch);
+                if used + w > target {
+                    break;
+                }
+                used += w;
+                out.push(ch);
+            }
+        }
+    }
+
+    out.push_str(ellipsis);
+    if styled {
+        out.push_str(RESET);
+    }
+    out
+}
+
+/// Styles in effect after a run of SGR sequences
+#[derive(Debug, Default)]
+struct SgrState {
+    fg: Option<String>,
+    bg: Option<String>,
+    attrs: Vec<String>,
+}
+
+impl SgrState {
+    fn apply(&mut self, params: &str) {
+        let mut params = params.split(';');
+        while let Some(param) = params.next() {
+            match param {
+                "" | "0" => *self = SgrState::default(),
+                "38" | "48" => {
+                    let mut color = vec![param];
+                    let count = match params.next() {
+                        Some("5") => Some(("5", 1)),
+                        Some("2") => Some(("2", 3)),
+                        _ => None,
+                    };
+                    if let Some((kind, count)) = count {
+                        color.push(kind);
+                        color.extend(params.by_ref().take(count));
+                    }
+                    let color = Some(color.join(";"));
+                    if param == "38" {
+                        self.fg = color;
+                    } else {
+                        self.bg = color;
+                    }
+                }
+                "39" => self.fg = None,
+                "49" => self.bg = None,
+                code => {
+                    let closed: &[&str] = match code {
+                        "22" => &["1", "2"],
+                        "23" => &["3"],
+                        "24" => &["4"],
+                        "27" => &["7"],
+                        "28" => &["8"],
+                        "29" => &["9"],
+                        "55" => &["53"],
+                        _ => &[],
+                    };
+                    let num: u8 = code.parse().unwrap_or(0);
+                    if !closed.is_empty() {
+                        self.attrs.retain(|a| !closed.contains(&a.as_str()));
+                    } else if (30..=37).contains(&num) || (90..=97).contains(&num) {
+                        self.fg = Some(String::from(code));
+                    } else if (40..=47).contains(&num) || (100..=107).contains(&num) {
+                        self.bg = Some(String::from(code));
+                    } else if !self.attrs.iter().any(|a| a == code) {
+                        self.attrs.push(String::from(code));
+                    }
+                }
+            }
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.fg.is_none() && self.bg.is_none() && self.attrs.is_empty()
+    }
+
+    /// Sequence that brings a reset terminal back to this state
+    fn open(&self) -> String {
+        if self.is_empty() {
+            return String::new();
+        }
+
+        let params: Vec<&str> = self
+            .attrs
+            .iter()
+            .chain(&self.fg)
+            .chain(&self.bg)
+            .map(String::as_str)
+            .collect();
+        format!("\x1b[{}m", params.join(";"))
+    }
+}
+
+struct Wrapper {
+    width: usize,
+    lines: Vec<String>,
+    line: String,
+    line_width: usize,
+    /// Styles in effect at the end of `line`
+    active: SgrState,
+}
+
+impl Wrapper {
+    fn break_line(&mut self) {
+        if !self.active.is_empty() {
+            self.line.push_str(RESET);
+        }
+        let next = self.active.open();
+        self.lines.push(std::mem::replace(&mut self.line, next));
+        self.line_width = 0;
+    }
+
+    fn push(&mut self, piece: Piece) {
+        match piece {
+            Piece::Escape(escape) => {
+                if is_sgr(escape) {
+                    self.active.apply(&escape[2..escape.len() - 1]);
+                }
+                self.line.push_str(escape);
+            }
+            Piece::Char(ch) => {
+                let w = char_width(ch);
+                if self.line_width > 0 && self.line_width + w > self.width {
+                    self.break_line();
+                }
+                self.line.push(ch);
+                self.line_width += w;
+            }
+        }
+    }
+
+    fn push_word(&mut self, word: &[Piece]) {
+        let w: usize = word
+            .iter()
+            .map(|piece| match piece {
+                Piece::Char(ch) => char_width(*ch),
+                Piece::Escape(_) => 0,
+            })
+            .sum();
+
+        if self.line_width > 0 && w > 0 {
+            if self.line_width + 1 + w > self.width {
+                self.break_line();
+            } else {
+                self.line.push(' ');
+                self.line_width += 1;
+            }
+        }
+
+        for piece in word {
+            self.push(*piece);
+        }
+    }
+}
+
+/// Split `s` into lines of at most `width` columns at spaces, breaking
+/// words longer than a line. Styles open at a break are closed at the end
+/// of the line and opened again on the next one.
+pub fn wrap(s: &str, width: usize) -> Vec<String> {
+    let mut wrapper = Wrapper {
+        width: width.max(1),
+        lines: vec![],
+        line: String::new(),
+        line_width: 0,
+        active: SgrState::default(),
+    };
+    let mut word: Vec<Piece> = vec![];
+
+    for piece in pieces(s) {
+        match piece {
+            Piece::Char(' ') => {
+                wrapper.push_word(&word);
+                word.clear();
+            }
+            Piece::Char('\n') => {
+                wrapper.push_word(&word);
+                word.clear();
+                wrapper.break_line();
+            }
+            piece => word.push(piece),
+        }
+    }
+
+    wrapper.push_word(&word);
+    wrapper.lines.push(wrapper.line);
+    wrapper.lines
+}
+
+/// Split `s` into rows of `width` columns the way the terminal would wrap
+/// it, without looking for spaces. Styles are carried over like in `wrap`.
+pub fn wrap_hard(s: &str, width: usize) -> Vec<String> {
+    let mut wrapper = Wrapper {
+        width: width.max(1),
+        lines: vec![],
+        line: String::new(),
+        line_width: 0,
+        active: SgrState::default(),
+    };
+
+    for piece in pieces(s) {
+        wrapper.push(piece);
+    }
+
+    wrapper.lines.push(wrapper.line);
+    wrapper.lines
+}
+
+/// Start two strings share, cut between pieces
+#[derive(Debug, Default, PartialEq)]
+pub struct Prefix {
+    /// Length in bytes
+    pub len: usize,
+    /// Columns it takes
+    pub width: usize,
+    /// Sequence bringing a reset terminal to the styles in effect after it
+    pub style: String,
+}
+
+pub fn common_prefix(a: &str, b: &str) -> Prefix {
+    let mut prefix = Prefix::default();
+    let mut state = SgrState::default();
+
+    for (piece, other) in pieces(a).into_iter().zip(pieces(b)) {
+        if piece != other {
+            break;
+        }
+
+        match piece {
+            Piece::Escape(escape) => {
+                if is_sgr(escape) {
+                    state.apply(&escape[2..escape.len() - 1]);
+                }
+                prefix.len += escape.len();
+            }
+            Piece::Char(ch) => {
+                prefix.len += ch.len_utf8();
+                prefix.width += char_width(ch);
+            }
+        }
+    }
+
+    prefix.style = state.open();
+    prefix
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn measure() {
+        let s = "\x1b[999D\x1b[2K\x1b[1;32m?\x1b[22;39m 日本 \x1b]8;;url\x07link\x1b]8;;\x07";
+        assert_eq!(strip(s), "? 日本 link");
+        assert_eq!(width(s), 11);
+    }
+
+    #[test]
+    pub fn cut() {
+        assert_eq!(truncate("hello", 5), "hello");
+        assert_eq!(truncate("hello world", 6), "hello…");
+        assert_eq!(
+            truncate("\x1b[31mhello world\x1b[39m", 4),
+            "\x1b[31mhel…\x1b[0m"
+        );
+    }
+
+    #[test]
+    pub fn wrapping() {
+        assert_eq!(
+            wrap("the quick brown fox", 10),
+            vec!["the quick", "brown fox"]
+        );
+        assert_eq!(wrap("abcdefgh", 3), vec!["abc", "def", "gh"]);
+        assert_eq!(
+            wrap("\x1b[31mred text\x1b[39m end", 4),
+            vec!["\x1b[31mred\x1b[0m", "\x1b[31mtext\x1b[39m", "end"]
+        );
+        assert_eq!(wrap_hard("ab cd", 2), vec!["ab", " c", "d"]);
+        assert_eq!(
+            wrap("\x1b[1;38;5;39mab\x1b[22mcd", 2),
+            vec!["\x1b[1;38;5;39mab\x1b[22m\x1b[0m", "\x1b[38;5;39mcd"]
+        );
+    }
+
+    #[test]
+    pub fn prefix() {
+        let prefix = common_prefix("\x1b[36mabc\x1b[39m", "\x1b[36mabd\x1b[39m");
+        assert_eq!(prefix.len, 7);
+        assert_eq!(prefix.width, 2);
+        assert_eq!(prefix.style, "\x1b[36m");
+        assert_eq!(common_prefix("日本", "日x").width, 2);
+    }
+}
@@ -0,0 +1,265 @@
+use std::collections::HashMap;
+use std::env;
+use std::sync::RwLock;
+
+#[cfg(feature = "serde")]
+use std::{
+    collections::BTreeMap,
+    fmt::{Display, Formatter},
+    fs, io,
+    path::Path,
+};
+
+use crate::PromptError;
+
+static ANSWERS: RwLock<Option<Answers>> = RwLock::new(None);
+
+#[cfg(feature = "serde")]
+#[derive(Debug)]
+pub enum AnswersError {
+    IO(io::Error),
+    Parse(String),
+}
+
+#[cfg(feature = "serde")]
+impl Display for AnswersError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AnswersError::IO(err) => err.fmt(f),
+            AnswersError::Parse(msg) => msg.fmt(f),
+        }
+    }
+}
+
+/// Answers given ahead of time, looked up by the `key` of each prompt
+/// before it is asked. Values are written the way they would be typed, e.g.
+/// `a, b` for a list or `1..5` for a range.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Answers {
+    pub values: HashMap<String, String>,
+    /// Read `PREFIX_KEY` variables too, they win over `values`
+    pub env_prefix: Option<String>,
+    /// Fail instead of asking when a prompt has no answer
+    pub strict: bool,
+    /// Answer every prompt as if enter was pressed right away, like `--yes`
+    pub accept_defaults: bool,
+    /// Keys of every prompt in the run, so strict mode reports all of those
+    /// without an answer at once instead of only the first one
+    pub keys: Vec<String>,
+}
+
+impl Answers {
+    pub fn new() -> Self {
+        Answers::default()
+    }
+
+    pub fn insert<K: Into<String>, V: Into<String>>(&mut self, key: K, value: V) {
+        self.values.insert(key.into(), value.into());
+    }
+
+    /// Variable answering `key`, e.g. `APP_DB_HOST` for `db.host`
+    pub fn env_name(prefix: &str, key: &str) -> String {
+        let key: String = key
+            .chars()
+            .map(|ch| {
+                if ch.is_ascii_alphanumeric() {
+                    ch.to_ascii_uppercase()
+                } else {
+                    '_'
+                }
+            })
+            .collect();
+
+        format!("{}_{}", prefix.to_ascii_uppercase(), key)
+    }
+
+    pub fn get(&self, key: &str) -> Option<String> {
+        self.get_with(key, |name| env::var(name).ok())
+    }
+
+    /// `get` reading variables through `var` instead of the process
+    /// environment
+    pub fn get_with<F: Fn(&str) -> Option<String>>(&self, key: &str, var: F) -> Option<String> {
+        self.env_prefix
+            .as_deref()
+            .and_then(|prefix| var(&Answers::env_name(prefix, key)))
+            .or_else(|| self.values.get(key).cloned())
+    }
+
+    /// Keys from `keys` that have no answer
+    pub fn missing<'k, I: IntoIterator<Item = &'k str>>(&self, keys: I) -> Vec<String> {
+        keys.into_iter()
+            .filter(|key| self.get(key).is_none())
+            .map(String::from)
+            .collect()
+    }
+
+    /// Check a whole run before asking anything, failing with every key
+    /// that has no answer
+    pub fn require<'k, I: IntoIterator<Item = &'k str>>(&self, keys: I) -> Result<(), PromptError> {
+        let missing = self.missing(keys);
+        if missing.is_empty() {
+            return Ok(());
+        }
+
+        Err(PromptError::MissingAnswers(missing))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl Answers {
+    /// Nested tables become dotted keys and lists are joined with `, `, with
+    /// commas in their items escaped
+    fn from_value(value: serde_json::Value) -> Self {
+        fn flatten(key: String, value: serde_json::Value, answers: &mut Answers) {
+            use serde_json::Value;
+
+            let text = match value {
+                Value::Object(map) => {
+                    for (name, value) in map {
+                        let name = if key.is_empty() {
+                            name
+                        } else {
+                            format!("{}.{}", key, name)
+                        };
+                        flatten(name, value, answers);
+                    }
+                    return;
+                }
+                Value::Array(items) => items
+                    .into_iter()
+                    .map(|item| match item {
+                        Value::String(s) => crate::escape_separators(&s, &[',']),
+                        other => other.to_string(),
+                    })
+                    .collect::<Vec<_>>()
+                    .join(", "),
+                Value::String(s) => s,
+                Value::Null => return,
+                other => other.to_string(),
+            };
+            answers.insert(key, text);
+        }
+
+        let mut answers = Answers::new();
+        flatten(String::new(), value, &mut answers);
+        answers
+    }
+
+    pub fn from_json(text: &str) -> Result<Self, AnswersError> {
+        serde_json::from_str(text)
+            .map(Answers::from_value)
+            .map_err(|err| AnswersError::Parse(err.to_string()))
+    }
+
+    pub fn from_yaml(text: &str) -> Result<Self, AnswersError> {
+        serde_yaml::from_str(text)
+            .map(Answers::from_value)
+            .map_err(|err| AnswersError::Parse(err.to_string()))
+    }
+
+    pub fn from_toml(text: &str) -> Result<Self, AnswersError> {
+        toml::from_str(text)
+            .map(Answers::from_value)
+            .map_err(|err| AnswersError::Parse(err.to_string()))
+    }
+
+    pub fn to_json(&self) -> String {
+        let values: BTreeMap<_, _> = self.values.iter().collect();
+        serde_json::to_string_pretty(&values).unwrap_or_default()
+    }
+
+    pub fn to_yaml(&self) -> String {
+        let values: BTreeMap<_, _> = self.values.iter().collect();
+        serde_yaml::to_string(&values).unwrap_or_default()
+    }
+
+    pub fn to_toml(&self) -> String {
+        let values: BTreeMap<_, _> = self.values.iter().collect();
+        toml::to_string(&values).unwrap_or_default()
+    }
+
+    /// Write `values` in the format `load` reads for the extension of `path`
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<(), AnswersError> {
+        let path = path.as_ref();
+        let text = match path.extension().and_then(|ext| ext.to_str()) {
+            Some(ext) if ext.eq_ignore_ascii_case("json") => self.to_json(),
+            Some(ext) if ext.eq_ignore_ascii_case("yaml") || ext.eq_ignore_ascii_case("yml") => {
+                self.to_yaml()
+            }
+            _ => self.to_toml(),
+        };
+
+        fs::write(path, text).map_err(AnswersError::IO)
+    }
+
+    /// Read `.json` and `.yaml`/`.yml` files by their extension and anything
+    /// else as TOML
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, AnswersError> {
+        let path = path.as_ref();
+        let text = fs::read_to_string(path).map_err(AnswersError::IO)?;
+
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some(ext) if ext.eq_ignore_ascii_case("json") => Answers::from_json(&text),
+            Some(ext) if ext.eq_ignore_ascii_case("yaml") || ext.eq_ignore_ascii_case("yml") => {
+                Answers::from_yaml(&text)
+            }
+            _ => Answers::from_toml(&text),
+        }
+    }
+}
+
+/// Answers every prompt looks at before asking
+pub fn set_answers(answers: Answers) {
+    *ANSWERS.write().unwrap() = Some(answers);
+}
+
+/// Run `f` with the answers given to `set_answers`
+pub fn with_answers<F: FnOnce(&Answers) -> V, V>(f: F) -> V {
+    match ANSWERS.read().unwrap().as_ref() {
+        Some(answers) => f(answers),
+        None => f(&Answers::default()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn lookup() {
+        let mut answers = Answers::new();
+        answers.insert("db.host", "localhost");
+        answers.env_prefix = Some(String::from("rusky_test"));
+        let var = |name: &str| (name == "RUSKY_TEST_DB_PORT").then(|| String::from("5432"));
+
+        assert_eq!(
+            answers.get_with("db.host", var).as_deref(),
+            Some("localhost")
+        );
+        assert_eq!(answers.get_with("db.port", var).as_deref(), Some("5432"));
+
+        // `require` reads the real environment
+        answers.insert("db.port", "5432");
+        assert!(matches!(
+            answers.require(["db.host", "db.user", "db.port", "db.name"]),
+            Err(PromptError::MissingAnswers(missing)) if missing == ["db.user", "db.name"]
+        ));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    pub fn load() {
+        let toml =
+            Answers::from_toml("name = \"app\"\n[db]\nport = 5432\ntags = [\"a\", \"b\"]").unwrap();
+        let yaml = Answers::from_yaml("name: app\ndb:\n  port: 5432\n  tags: [a, b]").unwrap();
+        let json =
+            Answers::from_json(r#"{"name": "app", "db": {"port": 5432, "tags": ["a", "b"]}}"#)
+                .unwrap();
+
+        assert_eq!(toml, yaml);
+        assert_eq!(toml, json);
+        assert_eq!(toml.get("db.tags").as_deref(), Some("a, b"));
+        assert_eq!(Answers::from_toml(&toml.to_toml()).unwrap(), toml);
+    }
+}
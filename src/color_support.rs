@@ -0,0 +1,130 @@
+use std::env;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+/// How many colors the terminal can show, ordered from none to truecolor
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ColorLevel {
+    /// No styling at all, not even bold or dim
+    None,
+    /// The 16 `Color` variants
+    Basic,
+    Ansi256,
+    TrueColor,
+}
+
+const UNSET: u8 = u8::MAX;
+
+static LEVEL: AtomicU8 = AtomicU8::new(UNSET);
+
+impl ColorLevel {
+    /// Level for the given environment, `tty` tells whether stdout is a
+    /// terminal
+    pub fn from_env<F: Fn(&str) -> Option<String>>(var: F, tty: bool) -> ColorLevel {
+        let set = |name: &str| var(name).is_some_and(|v| !v.is_empty());
+
+        if set("NO_COLOR") {
+            return ColorLevel::None;
+        }
+
+        let forced = var("CLICOLOR_FORCE").is_some_and(|v| !v.is_empty() && v != "0");
+        if !tty && !forced {
+            return ColorLevel::None;
+        }
+
+        let term = var("TERM").unwrap_or_default();
+        let colorterm = var("COLORTERM").unwrap_or_default().to_lowercase();
+
+        if term == "dumb" && !forced {
+            ColorLevel::None
+        } else if colorterm == "truecolor" || colorterm == "24bit" || term.ends_with("-direct") {
+            ColorLevel::TrueColor
+        } else if term.contains("256color") {
+            ColorLevel::Ansi256
+        } else {
+            ColorLevel::Basic
+        }
+    }
+
+    /// Level for this process' environment and stdout
+    pub fn detect() -> ColorLevel {
+        ColorLevel::from_env(
+            |name| env::var(name).ok(),
+            termion::is_tty(&std::io::stdout()),
+        )
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        *self != ColorLevel::None
+    }
+}
+
+/// Level every `Color`, `Modifier` and `Style` is written for. It is detected
+/// the first time it is needed unless `set_color_level` was called before.
+pub fn color_level() -> ColorLevel {
+    match LEVEL.load(Ordering::Relaxed) {
+        0 => ColorLevel::None,
+        1 => ColorLevel::Basic,
+        2 => ColorLevel::Ansi256,
+        3 => ColorLevel::TrueColor,
+        _ => {
+            let level = ColorLevel::detect();
+            set_color_level(level);
+            level
+        }
+    }
+}
+
+/// Override the detected level, e.g. from a `--color` flag
+pub fn set_color_level(level: ColorLevel) {
+    LEVEL.store(level as u8, Ordering::Relaxed);
+}
+
+/// Run `f` with `level` set, putting the previous level back after it
+#[cfg(test)]
+pub(crate) fn with_color_level<F: FnOnce() -> V, V>(level: ColorLevel, f: F) -> V {
+    let _lock = crate::lock_globals();
+    let previous = LEVEL.swap(level as u8, Ordering::Relaxed);
+    let value = f();
+    LEVEL.store(previous, Ordering::Relaxed);
+
+    value
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn level(vars: &[(&str, &str)], tty: bool) -> ColorLevel {
+        ColorLevel::from_env(
+            |name| {
+                vars.iter()
+                    .find(|(k, _)| *k == name)
+                    .map(|(_, v)| v.to_string())
+            },
+            tty,
+        )
+    }
+
+    #[test]
+    pub fn detection() {
+        assert_eq!(level(&[("TERM", "xterm")], true), ColorLevel::Basic);
+        assert_eq!(
+            level(&[("TERM", "xterm-256color")], true),
+            ColorLevel::Ansi256
+        );
+        assert_eq!(
+            level(&[("COLORTERM", "truecolor")], true),
+            ColorLevel::TrueColor
+        );
+        assert_eq!(
+            level(&[("TERM", "xterm-256color")], false),
+            ColorLevel::None
+        );
+        assert_eq!(level(&[("CLICOLOR_FORCE", "1")], false), ColorLevel::Basic);
+        assert_eq!(
+            level(&[("NO_COLOR", "1"), ("CLICOLOR_FORCE", "1")], true),
+            ColorLevel::None
+        );
+        assert_eq!(level(&[("TERM", "dumb")], true), ColorLevel::None);
+    }
+}
@@ -1,26 +1,81 @@
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use crate::{color_level, ColorLevel};
+
+macro_rules! field_type {
+    ($field: ident) => {
+        u8
+    };
+}
+
 macro_rules! make_colors {
     ($cat: ident; $($name: ident = $open: expr, $close: expr);+ $(;)?) => {
+        make_colors! {
+            $cat;
+            extended {}
+            $($name = $open, $close);+
+        }
+    };
+    (
+        $cat: ident;
+        extended { $($(#[$meta: meta])* $ext: ident($($field: ident),+) = $ext_code: expr, $ext_open: expr, $ext_close: expr);* $(;)? }
+        $($name: ident = $open: expr, $close: expr);+ $(;)?
+    ) => {
+        #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
         pub enum $cat {
-            $($name),+
+            $($name,)+
+            $($(#[$meta])* $ext($(field_type!($field)),+)),*
         }
 
         impl $cat {
+            /// Every variant without parameters
+            pub const VARIANTS: &'static [$cat] = &[$($cat :: $name),+];
+
+            pub fn name(&self) -> &'static str {
+                match self {
+                    $($cat :: $name => stringify!($name),)+
+                    $($cat :: $ext(..) => stringify!($ext)),*
+                }
+            }
+
+            /// SGR codes that open and close the style. Variants with
+            /// parameters only give the code starting their sequence, see
+            /// `get_params`.
             pub fn get_parts(&self) -> (u8, u8) {
                 match self {
-                    $(
-                        $cat :: $name => ($open, $close)
-                    ),+
+                    $($cat :: $name => ($open, $close),)+
+                    $($cat :: $ext(..) => ($ext_code, $ext_close)),*
+                }
+            }
+
+            /// SGR parameters that open and close the style, such as
+            /// `38;5;208` for `Color::Ansi256(208)`
+            pub fn get_params(&self) -> (String, String) {
+                match self {
+                    $($cat :: $name => ($open.to_string(), $close.to_string()),)+
+                    $($cat :: $ext($($field),+) => ($ext_open, $ext_close.to_string())),*
                 }
             }
 
+            /// Empty when colors are disabled
             pub fn get_open(&self) -> String {
-                let (open, _) = self.get_parts();
+                let level = color_level();
+                if !level.is_enabled() {
+                    return String::new();
+                }
+                let (open, _) = self.for_level(level).get_params();
 
                 format!("\x1b[{}m", open)
             }
 
+            /// Empty when colors are disabled
             pub fn get_close(&self) -> String {
-                let (_, close) = self.get_parts();
+                let level = color_level();
+                if !level.is_enabled() {
+                    return String::new();
+                }
+                let (_, close) = self.for_level(level).get_params();
 
                 format!("\x1b[{}m", close)
             }
@@ -33,7 +88,6 @@ macro_rules! make_colors {
             pub fn a<S: Into<String>>(&self, text: S) -> String {
                 format!("{}{}{}", self.get_open(), text.into(), self.get_close())
             }
-
         }
 
         impl ::std::fmt::Display for $cat {
@@ -46,6 +100,11 @@ macro_rules! make_colors {
 
 make_colors! {
     Color;
+    extended {
+        /// Index into the 256 color palette
+        Ansi256(idx) = 38, format!("38;5;{}", idx), 39;
+        Rgb(r, g, b) = 38, format!("38;2;{};{};{}", r, g, b), 39;
+    }
     Black = 30, 39;
     Red = 31, 39;
     Green = 32, 39;
@@ -77,3 +136,287 @@ make_colors! {
     Hidden = 8, 28;
     Striketrough = 9, 29;
 }
+
+#[derive(Debug)]
+pub struct ColorParseErr;
+
+impl Display for ColorParseErr {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        "The value should be a color name, 0-255 or a hex code. (#rrggbb)".fmt(f)
+    }
+}
+
+/// RGB of the `Color` variants as xterm shows them, in `Color::VARIANTS`
+/// order
+const BASIC_RGB: [(u8, u8, u8); 16] = [
+    (0, 0, 0),
+    (205, 0, 0),
+    (0, 205, 0),
+    (205, 205, 0),
+    (0, 0, 238),
+    (205, 0, 205),
+    (0, 205, 205),
+    (229, 229, 229),
+    (127, 127, 127),
+    (255, 0, 0),
+    (0, 255, 0),
+    (255, 255, 0),
+    (92, 92, 255),
+    (255, 0, 255),
+    (0, 255, 255),
+    (255, 255, 255),
+];
+
+/// Channel values of the 6x6x6 cube in the 256 color palette
+const CUBE_STEPS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+fn cube_step(value: u8) -> usize {
+    CUBE_STEPS
+        .iter()
+        .enumerate()
+        .min_by_key(|(_, step)| (**step as i32 - value as i32).abs())
+        .map(|(i, _)| i)
+        .unwrap_or(0)
+}
+
+fn distance((r1, g1, b1): (u8, u8, u8), (r2, g2, b2): (u8, u8, u8)) -> i32 {
+    let d = |a: u8, b: u8| (a as i32 - b as i32).pow(2);
+    d(r1, r2) + d(g1, g2) + d(b1, b2)
+}
+
+impl Modifier {
+    /// Modifiers look the same at every level
+    pub fn for_level(&self, _level: ColorLevel) -> Modifier {
+        *self
+    }
+}
+
+impl Color {
+    /// RGB the color is shown as, using xterm's palette for indexed colors
+    pub fn to_rgb(&self) -> (u8, u8, u8) {
+        match *self {
+            Color::Rgb(r, g, b) => (r, g, b),
+            Color::Ansi256(idx @ 0..=15) => BASIC_RGB[idx as usize],
+            Color::Ansi256(idx @ 16..=231) => {
+                let idx = (idx - 16) as usize;
+                (
+                    CUBE_STEPS[idx / 36],
+                    CUBE_STEPS[idx / 6 % 6],
+                    CUBE_STEPS[idx % 6],
+                )
+            }
+            Color::Ansi256(idx) => {
+                let gray = 8 + 10 * (idx - 232);
+                (gray, gray, gray)
+            }
+            color => {
+                let idx = Color::VARIANTS.iter().position(|c| *c == color);
+                BASIC_RGB[idx.unwrap_or(0)]
+            }
+        }
+    }
+
+    /// Nearest color the terminal can show at `level`
+    pub fn for_level(&self, level: ColorLevel) -> Color {
+        match (*self, level) {
+            (Color::Rgb(r, g, b), ColorLevel::Ansi256) => {
+                let cube = 16 + 36 * cube_step(r) + 6 * cube_step(g) + cube_step(b);
+                let avg = (r as u32 + g as u32 + b as u32) / 3;
+                let gray = 232 + (avg.saturating_sub(3) / 10).min(23) as usize;
+
+                let candidates = [cube, gray];
+                let idx = candidates
+                    .iter()
+                    .min_by_key(|idx| distance(Color::Ansi256(**idx as u8).to_rgb(), (r, g, b)))
+                    .copied()
+                    .unwrap_or(cube);
+                Color::Ansi256(idx as u8)
+            }
+            (Color::Rgb(..) | Color::Ansi256(..), ColorLevel::Basic | ColorLevel::None) => {
+                let rgb = self.to_rgb();
+                let idx = BASIC_RGB
+                    .iter()
+                    .enumerate()
+                    .min_by_key(|(_, basic)| distance(**basic, rgb))
+                    .map(|(i, _)| i)
+                    .unwrap_or(0);
+                Color::VARIANTS[idx]
+            }
+            (color, _) => color,
+        }
+    }
+
+    /// Read `#rrggbb` or `#rgb`, the `#` is optional
+    pub fn from_hex(hex: &str) -> Option<Color> {
+        let hex = hex.strip_prefix('#').unwrap_or(hex);
+        if !hex.chars().all(|ch| ch.is_ascii_hexdigit()) {
+            return None;
+        }
+
+        let channel = |range: &str| u8::from_str_radix(range, 16).ok();
+        match hex.len() {
+            6 => Some(Color::Rgb(
+                channel(&hex[0..2])?,
+                channel(&hex[2..4])?,
+                channel(&hex[4..6])?,
+            )),
+            3 => {
+                let short = |i: usize| channel(&hex[i..i + 1]).map(|v| v * 17);
+                Some(Color::Rgb(short(0)?, short(1)?, short(2)?))
+            }
+            _ => None,
+        }
+    }
+
+    /// Same color painting the background
+    pub fn bg(self) -> Bg {
+        Bg(self)
+    }
+}
+
+impl FromStr for Color {
+    type Err = ColorParseErr;
+
+    /// Read a variant name, a 256 palette index such as `208` or a hex code
+    /// such as `#ff8800`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if let Some(color) = Color::VARIANTS
+            .iter()
+            .find(|c| c.name().eq_ignore_ascii_case(s))
+        {
+            return Ok(*color);
+        }
+
+        if let Ok(idx) = s.parse::<u8>() {
+            return Ok(Color::Ansi256(idx));
+        }
+
+        match s.strip_prefix('#') {
+            Some(hex) => Color::from_hex(hex).ok_or(ColorParseErr),
+            None => Err(ColorParseErr),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Color {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let text = String::deserialize(deserializer)?;
+        text.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+/// A `Color` painting the background, made with `Color::bg`
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Bg(pub Color);
+
+impl Bg {
+    pub fn for_level(&self, level: ColorLevel) -> Bg {
+        Bg(self.0.for_level(level))
+    }
+
+    /// SGR parameters that open and close the style
+    pub fn get_params(&self) -> (String, String) {
+        let (open, _) = self.0.get_params();
+        // 30-37 and 90-97 move to 40-47 and 100-107, `38;...` to `48;...`
+        let open = match open.parse::<u8>() {
+            Ok(code) => (code + 10).to_string(),
+            Err(_) => open.replacen("38", "48", 1),
+        };
+
+        (open, String::from("49"))
+    }
+
+    /// Empty when colors are disabled
+    pub fn get_open(&self) -> String {
+        let level = color_level();
+        if !level.is_enabled() {
+            return String::new();
+        }
+        let (open, _) = self.for_level(level).get_params();
+
+        format!("\x1b[{}m", open)
+    }
+
+    /// Empty when colors are disabled
+    pub fn get_close(&self) -> String {
+        let level = color_level();
+        if !level.is_enabled() {
+            return String::new();
+        }
+        let (_, close) = self.for_level(level).get_params();
+
+        format!("\x1b[{}m", close)
+    }
+
+    pub fn apply_color<S: Into<String>>(&self, text: S) -> String {
+        format!("{}{}{}", self.get_open(), text.into(), self.get_close())
+    }
+
+    /// Apply color - shothand
+    pub fn a<S: Into<String>>(&self, text: S) -> String {
+        format!("{}{}{}", self.get_open(), text.into(), self.get_close())
+    }
+}
+
+impl Display for Bg {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        self.get_open().fmt(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn parse() {
+        assert_eq!("redbright".parse::<Color>().ok(), Some(Color::RedBright));
+        assert_eq!("208".parse::<Color>().ok(), Some(Color::Ansi256(208)));
+        assert_eq!(
+            "#ff8800".parse::<Color>().ok(),
+            Some(Color::Rgb(255, 136, 0))
+        );
+        assert_eq!(Color::from_hex("f80"), Some(Color::Rgb(255, 136, 0)));
+        assert!("#ff88".parse::<Color>().is_err());
+        assert!("300".parse::<Color>().is_err());
+    }
+
+    #[test]
+    pub fn downgrade() {
+        let orange = Color::Rgb(255, 136, 0);
+        assert_eq!(orange.for_level(ColorLevel::TrueColor), orange);
+        assert_eq!(orange.for_level(ColorLevel::Ansi256), Color::Ansi256(208));
+        assert_eq!(orange.for_level(ColorLevel::Basic), Color::Yellow);
+        assert_eq!(
+            Color::Rgb(128, 128, 128).for_level(ColorLevel::Ansi256),
+            Color::Ansi256(244)
+        );
+        assert_eq!(
+            Color::Ansi256(9).for_level(ColorLevel::Basic),
+            Color::RedBright
+        );
+        assert_eq!(Color::Ansi256(21).for_level(ColorLevel::Basic), Color::Blue);
+    }
+
+    #[test]
+    pub fn codes() {
+        let params = |open: &str, close: &str| (String::from(open), String::from(close));
+        assert_eq!(Color::Rgb(1, 2, 3).get_params(), params("38;2;1;2;3", "39"));
+        assert_eq!(Color::Red.bg().get_params(), params("41", "49"));
+        assert_eq!(Color::CyanBright.bg().get_params(), params("106", "49"));
+        assert_eq!(
+            Color::Rgb(1, 2, 3).bg().get_params(),
+            params("48;2;1;2;3", "49")
+        );
+
+        assert_eq!(Color::Red.get_parts(), (31, 39));
+        assert_eq!(Modifier::Bold.get_parts(), (1, 22));
+        assert_eq!(Color::Ansi256(208).get_parts(), (38, 39));
+        assert_eq!(
+            Color::Ansi256(208).get_params(),
+            (String::from("38;5;208"), String::from("39"))
+        );
+    }
+}
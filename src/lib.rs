@@ -4,15 +4,36 @@ pub use termion::input::TermRead;
 pub extern crate rusky_icons;
 pub use rusky_icons::*;
 
+pub mod ansi;
+mod answers;
+pub mod color_support;
 pub mod colors;
 mod from_str;
 pub mod macros;
 mod my_terminal;
 pub mod preload;
 pub mod prompts;
+pub mod resize;
+mod session;
+pub mod style;
 pub mod themes;
 
+pub use answers::*;
+pub use color_support::*;
 pub use colors::*;
 pub use from_str::*;
-pub use my_terminal::MyTerminal;
+pub use my_terminal::{MyTerminal, Output};
 pub use prompts::*;
+pub use resize::*;
+pub use session::*;
+pub use style::*;
+
+/// Held by the tests that change process wide state, such as the session
+/// being recorded or the answers, since tests run in parallel
+#[cfg(test)]
+pub(crate) fn lock_globals() -> std::sync::MutexGuard<'static, ()> {
+    static GLOBALS: std::sync::Mutex<()> = std::sync::Mutex::new(());
+
+    // A failed test must not fail every test after it
+    GLOBALS.lock().unwrap_or_else(|err| err.into_inner())
+}
@@ -16,6 +16,118 @@ macro_rules! match_prompt_type_struct {
     ([$(U)? Float] $($tail:tt)+) => {
         $crate::NumberPrompt $($tail)+
     };
+    ([MultiLine] { $($fields:tt)+ }) => {
+        $crate::MultilinePrompt {
+            $($fields)+,
+            line_numbers: false,
+            editor: false,
+        }
+    };
+    ([Date] { $($fields:tt)+ }) => {
+        $crate::DatePrompt::<_, $crate::Date> {
+            $($fields)+,
+            min: Option::None,
+            max: Option::None,
+            format: Option::None,
+            calendar: false,
+        }
+    };
+    ([Time] { $($fields:tt)+ }) => {
+        $crate::DatePrompt::<_, $crate::Time> {
+            $($fields)+,
+            min: Option::None,
+            max: Option::None,
+            format: Option::None,
+            calendar: false,
+        }
+    };
+    ([DateTime] { $($fields:tt)+ }) => {
+        $crate::DatePrompt::<_, $crate::DateTime> {
+            $($fields)+,
+            min: Option::None,
+            max: Option::None,
+            format: Option::None,
+            calendar: false,
+        }
+    };
+    ([Mask] { $($fields:tt)+ }) => {
+        $crate::MaskPrompt {
+            $($fields)+,
+            mask: $crate::Mask::default(),
+            raw: false,
+        }
+    };
+    // Sliders go from 0 to 100 unless `min` and `max` are set
+    ([Slider] { $($fields:tt)+ }) => {
+        $crate::SliderPrompt {
+            $($fields)+,
+            min: $crate::SliderValue::from_f64(0.0),
+            max: $crate::SliderValue::from_f64(100.0),
+            step: Option::None,
+            width: 30,
+        }
+    };
+    ([Range] { $($fields:tt)+ }) => {
+        $crate::RangePrompt {
+            $($fields)+,
+            min: $crate::SliderValue::from_f64(0.0),
+            max: $crate::SliderValue::from_f64(100.0),
+            step: Option::None,
+            width: 30,
+        }
+    };
+    ([Sort] { $($fields:tt)+ }) => {
+        $crate::SortPrompt {
+            $($fields)+,
+            items: Vec::new(),
+            page_size: 7,
+        }
+    };
+    ([Tree] { $($fields:tt)+ }) => {
+        $crate::TreePrompt {
+            $($fields)+,
+            nodes: Vec::new(),
+            multi: false,
+            page_size: 10,
+        }
+    };
+    ([Table] { $($fields:tt)+ }) => {
+        $crate::TablePrompt {
+            $($fields)+,
+            columns: Vec::new(),
+            rows: Vec::new(),
+            page_size: 10,
+        }
+    };
+    ([KeyValue] { $($fields:tt)+ }) => {
+        $crate::KeyValuePrompt {
+            $($fields)+,
+            entries: Vec::new(),
+            key_pattern: None,
+            secret_keys: vec![
+                String::from("*_TOKEN"),
+                String::from("*_PASSWORD"),
+                String::from("*_SECRET"),
+            ],
+            page_size: 10,
+        }
+    };
+    ([List] { $($fields:tt)+ }) => {
+        $crate::ListInputPrompt::<_, String> {
+            $($fields)+,
+            item_type: $crate::PromptType::String,
+            validator: None,
+            min: 0,
+            max: None,
+            chips: false,
+        }
+    };
+    ([Color] { $($fields:tt)+ }) => {
+        $crate::ColorPrompt {
+            $($fields)+,
+            sample: String::from("The quick brown fox jumps over the lazy dog"),
+        }
+    };
     ([$($_:ident)?] $($tail:tt)+) => {
         $crate::TextPrompt $($tail)+
     };
@@ -26,6 +138,7 @@ macro_rules! create_prompt {
     ($prefix:tt $text:expr; $(($($extra:expr)+))? $([$($default:expr)+])? $({ $THEME: expr })? $($type:ident)?) => {
         $crate::match_prompt_type_struct!([$($type)?] {
             base: &mut $crate::PromptBase {
+                key: Option::None,
                 prefix: Into::into(stringify!($prefix)),
                 text: Into::into($text),
                 default: $crate::handle_optional!(if ($($($default)+)?) {
@@ -33,17 +146,21 @@ macro_rules! create_prompt {
                 } else {
                     Option::None
                 }),
+                initial: Option::None,
+                placeholder: Option::None,
                 extra: $crate::handle_optional!(if ($($($extra)+)?) {
                     Option::Some(Into::into($($($extra)+)?))
                 } else {
                     $crate::match_prompt_type_extra!($($type)?)
                 }),
+                help: Option::None,
                 line: Option::None,
                 prompt_type: $crate::handle_optional!(if ($($type)?) {
                     $crate::PromptType:: $($type)?
                 } else {
                     $crate::PromptType::String
                 }),
+                finish: $crate::FinishMode::Summary,
             },
             theme: $crate::handle_optional!(if ($($THEME)?) {
                 $($THEME)?
@@ -1,8 +1,13 @@
 use std::{
-    io::{self, Write},
+    io::{self, Stdout, Write},
     ops::{Deref, DerefMut},
 };
 
+use termion::input::MouseTerminal;
+use termion::raw::{IntoRawMode, RawTerminal};
+
+use crate::{is_interactive, set_plain_mode};
+
 pub struct MyTerminal<W: Write> {
     term: W,
 }
@@ -67,3 +72,41 @@ impl<W: Write> Write for MyTerminal<W> {
         self.term.flush()
     }
 }
+
+/// Stdout for the prompts: the terminal in raw mode, or plain stdout when
+/// there is no terminal and the prompts read lines instead
+pub enum Output {
+    Terminal(MyTerminal<MouseTerminal<RawTerminal<Stdout>>>),
+    Plain(Stdout),
+}
+
+impl Output {
+    /// Put the terminal in raw mode, or turn on `plain_mode` when stdin or
+    /// stdout is not a terminal. Used by `create_stdout!`.
+    pub fn stdout() -> Output {
+        if is_interactive() {
+            if let Ok(raw) = io::stdout().into_raw_mode() {
+                return Output::Terminal(MyTerminal::from(MouseTerminal::from(raw)));
+            }
+        }
+
+        set_plain_mode(true);
+        Output::Plain(io::stdout())
+    }
+}
+
+impl Write for Output {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match self {
+            Output::Terminal(term) => term.write(buf),
+            Output::Plain(stdout) => stdout.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self {
+            Output::Terminal(term) => term.flush(),
+            Output::Plain(stdout) => stdout.flush(),
+        }
+    }
+}
@@ -10,15 +10,22 @@ pub use crate::TermRead;
 #[macro_export]
 macro_rules! create_stdout {
     () => {
-        MyTerminal::from(MouseTerminal::from(stdout().into_raw_mode().unwrap()))
+        $crate::Output::stdout()
     };
 }
 
+/// Events of stdin. With `resize` the prompts redraw when the terminal is
+/// resized, which installs a `SIGWINCH` handler while the events are alive,
+/// see `watch_resize`.
 #[macro_export]
 macro_rules! create_events {
     () => {{
         let stdin = ::std::io::stdin();
-        $crate::TermRead::events(stdin)
+        $crate::TermRead::events($crate::ResizeReader::new(stdin))
+    }};
+    (resize) => {{
+        let stdin = ::std::io::stdin();
+        $crate::TermRead::events($crate::ResizeReader::watching(stdin))
     }};
 }
 
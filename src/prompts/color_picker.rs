@@ -0,0 +1,263 @@
+use std::io::{Read, Write};
+
+use termion::event::{Event, Key};
+use termion::input::Events;
+
+use crate::themes::FormatTheme;
+use crate::{
+    io_handl, is_resize, Color, ColorParseErr, Frame, FrameWriter, LineEditor, PromptBase,
+    PromptError, PromptType, Style,
+};
+
+/// Swatches per row, the normal colors first and the bright ones below
+const GRID_WIDTH: usize = 8;
+
+/// `color` written the way `Color::from_str` reads it
+fn written(color: Color) -> String {
+    match color {
+        Color::Ansi256(idx) => idx.to_string(),
+        Color::Rgb(r, g, b) => format!("#{:02x}{:02x}{:02x}", r, g, b),
+        color => String::from(color.name()),
+    }
+}
+
+/// A swatch is labelled with its index, so the grid can still be read
+/// without colors
+fn swatch<T: FormatTheme>(theme: &T, idx: usize, color: Color, selected: bool) -> String {
+    let (r, g, b) = color.to_rgb();
+    let light = r as u32 * 299 + g as u32 * 587 + b as u32 * 114 > 128_000;
+    let text = if light { Color::Black } else { Color::White };
+    let block = Style::new().fg(text).bg(color).a(format!("{:^4}", idx));
+    if selected {
+        format!(
+            "{}{}{}",
+            theme.format_pointer("["),
+            block,
+            theme.format_pointer("]")
+        )
+    } else {
+        format!(" {} ", block)
+    }
+}
+
+/// Pick one of the 16 `Color` swatches with the arrows, or press Tab to type
+/// a 256 color index or a hex code. The sample text previews the choice.
+#[derive(Debug)]
+pub struct ColorPrompt<'a, T: FormatTheme> {
+    pub base: &'a mut PromptBase,
+    pub theme: &'a T,
+    /// Text painted with the current color
+    pub sample: String,
+}
+
+impl<'a, T: FormatTheme> ColorPrompt<'a, T> {
+    pub fn prompt<R: Read, W: Write>(
+        &mut self,
+        stdin: &mut Events<R>,
+        stdout: &mut W,
+        go_back: Option<bool>,
+    ) -> Result<Color, PromptError> {
+        if let Some(answer) = self.base.resolve(self.theme, stdin, stdout, |line| {
+            line.parse::<Color>()
+                .map_err(|err: ColorParseErr| err.to_string())
+        }) {
+            return answer;
+        }
+
+        io_handl!(self.base.start_line(stdout, go_back.unwrap_or(true)));
+
+        let swatches = Color::VARIANTS;
+        let mut cursor: usize = 0;
+        let mut custom: Option<LineEditor> = None;
+
+        match self.base.default.as_deref().map(str::parse::<Color>) {
+            Some(Ok(color @ (Color::Ansi256(_) | Color::Rgb(..)))) => {
+                custom = Some(LineEditor::with_value(written(color)));
+            }
+            Some(Ok(color)) => {
+                cursor = swatches.iter().position(|c| *c == color).unwrap_or(0);
+            }
+            _ => {}
+        }
+
+        let mut writer = FrameWriter::new();
+
+        macro_rules! current {
+            () => {
+                match &custom {
+                    Some(editor) => editor.value().parse::<Color>().ok(),
+                    None => Some(swatches[cursor]),
+                }
+            };
+        }
+
+        macro_rules! update {
+            () => {
+                let hint = if custom.is_some() {
+                    "(#rrggbb, #rgb or 0-255, tab for swatches)"
+                } else {
+                    "(arrows to move, tab for hex or 256 colors)"
+                };
+                let mut frame = Frame::new();
+                frame.push(format!(
+                    "{}{}",
+                    self.theme.format_prompt(self.base),
+                    self.theme.format_hint(hint)
+                ));
+
+                for (r, colors) in swatches.chunks(GRID_WIDTH).enumerate() {
+                    let line: Vec<String> = colors
+                        .iter()
+                        .enumerate()
+                        .map(|(c, color)| {
+                            swatch(
+                                self.theme,
+                                r * GRID_WIDTH + c,
+                                *color,
+                                custom.is_none() && r * GRID_WIDTH + c == cursor,
+                            )
+                        })
+                        .collect();
+                    frame.push(format!("  {}", line.concat()));
+                }
+
+                let preview = match current!() {
+                    Some(color) => format!(
+                        "{} {}",
+                        color.a(&self.sample),
+                        self.theme.format_hint(&written(color))
+                    ),
+                    None => self.theme.format_error("Not a color"),
+                };
+                match &custom {
+                    Some(editor) => {
+                        frame.push(format!(
+                            "  {} {}",
+                            self.theme.format_hint("Color:"),
+                            self.theme.format_input(&editor.value())
+                        ));
+                        frame.cursor_here(editor.cursor_back());
+                        frame.push(format!("  {}", preview));
+                    }
+                    None => frame.push(format!("  {}", preview)),
+                }
+                self.base.add_help(self.theme, &mut frame);
+                io_handl!(writer.draw(stdout, &frame));
+            };
+        }
+
+        macro_rules! finish {
+            ($line:expr) => {
+                io_handl!(writer.finish_with(stdout, $line));
+            };
+        }
+
+        update!();
+
+        for c in stdin {
+            let evt = c.unwrap();
+            if is_resize(&evt) {
+                io_handl!(writer.redraw(stdout));
+                continue;
+            }
+            match evt {
+                Event::Key(Key::Ctrl('c') | Key::Esc) => {
+                    finish!(self.theme.format_cancelled(self.base));
+                    return Err(PromptError::KeyboardInterrupt);
+                }
+                Event::Key(Key::Char('\n')) => match current!() {
+                    Some(color) => {
+                        self.base.record(&written(color));
+                        finish!(self
+                            .base
+                            .answered_line(self.theme, &color.a(written(color))));
+                        return Ok(color);
+                    }
+                    None => continue,
+                },
+                Event::Key(Key::Char('\t') | Key::BackTab) => {
+                    custom = match custom {
+                        Some(_) => None,
+                        None => Some(LineEditor::with_value("#")),
+                    };
+                }
+                Event::Key(key) if custom.is_some() => {
+                    if let Some(editor) = &mut custom {
+                        if !editor.handle(&key) {
+                            continue;
+                        }
+                    }
+                }
+                Event::Key(Key::Left) => cursor = cursor.saturating_sub(1),
+                Event::Key(Key::Right) => cursor = (cursor + 1).min(swatches.len() - 1),
+                Event::Key(Key::Up) => cursor = cursor.saturating_sub(GRID_WIDTH),
+                Event::Key(Key::Down) if cursor + GRID_WIDTH < swatches.len() => {
+                    cursor += GRID_WIDTH;
+                }
+                Event::Key(Key::Home) => cursor = 0,
+                Event::Key(Key::End) => cursor = swatches.len() - 1,
+                _ => continue,
+            };
+
+            update!();
+        }
+
+        Err(PromptError::Unanswered(self.base.text.clone()))
+    }
+
+    pub fn prompt_handled<R: Read, W: Write>(
+        &mut self,
+        stdin: &mut Events<R>,
+        stdout: &mut W,
+        go_back: Option<bool>,
+    ) -> Result<Color, PromptError> {
+        match &self.base.prompt_type {
+            PromptType::Color => {}
+            _ => {
+                return Err(PromptError::Custom(String::from(
+                    "Prompt struct only can be Color",
+                )));
+            }
+        };
+
+        self.prompt(stdin, stdout, go_back)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{themes::DefaultTheme, TermRead};
+
+    #[test]
+    pub fn written_back() {
+        for text in ["RedBright", "208", "#ff8800"] {
+            assert_eq!(written(text.parse().unwrap()), text);
+        }
+    }
+
+    #[test]
+    pub fn picking() {
+        let pick = |keys: &[u8]| {
+            let mut base = PromptBase::test(PromptType::Color);
+            let mut out = Vec::new();
+            let color = ColorPrompt {
+                base: &mut base,
+                theme: &DefaultTheme,
+                sample: String::from("Sample"),
+            }
+            .prompt(&mut keys.events(), &mut out, Some(false));
+            (color, crate::ansi::strip(&String::from_utf8(out).unwrap()))
+        };
+
+        let (color, out) = pick(b"\x1b[C\x1b[B\r");
+        assert_eq!(color.unwrap(), Color::RedBright);
+        assert!(out.contains(" 15 "));
+
+        let (color, _) = pick(b"\t\x7f#f80\r");
+        assert_eq!(color.unwrap(), Color::Rgb(255, 136, 0));
+
+        let (color, _) = pick(b"\x1b[C");
+        assert!(matches!(color, Err(PromptError::Unanswered(_))));
+    }
+}
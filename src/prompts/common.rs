@@ -1,8 +1,13 @@
 use std::io;
+use std::path::Path;
+
+use crate::{Date, DateTime, MyFromStr, Time};
 
 #[derive(Clone, Debug, PartialEq)]
 pub enum PromptType {
     String,
+    /// Text shown as `*` while typed and once answered
+    Password,
     Bool,
     Int,
     UInt,
@@ -10,11 +15,90 @@ pub enum PromptType {
     UFloat,
     FilePath,
     FilePathExisting,
+    MultiLine,
+    Date,
+    Time,
+    DateTime,
+    Mask,
+    Slider,
+    Range,
+    Sort,
+    Tree,
+    Table,
+    KeyValue,
+    List,
+    Color,
+}
+
+impl PromptType {
+    /// Check that `value` can be read as this type, returning the message
+    /// to show when it cannot
+    pub fn validate(&self, value: &str) -> Result<(), String> {
+        let valid = match self {
+            PromptType::Bool => <bool as MyFromStr>::from_str(value).is_ok(),
+            PromptType::Int => <i64 as MyFromStr>::from_str(value).is_ok(),
+            PromptType::UInt => <u64 as MyFromStr>::from_str(value).is_ok(),
+            PromptType::Float => <f64 as MyFromStr>::from_str(value).is_ok(),
+            PromptType::UFloat => <f64 as MyFromStr>::from_str(value).is_ok_and(|v| v >= 0.0),
+            PromptType::FilePathExisting => Path::new(value).exists(),
+            PromptType::Date => <Date as MyFromStr>::from_str(value).is_ok(),
+            PromptType::Time => <Time as MyFromStr>::from_str(value).is_ok(),
+            PromptType::DateTime => <DateTime as MyFromStr>::from_str(value).is_ok(),
+            _ => !value.is_empty(),
+        };
+
+        if valid {
+            return Ok(());
+        }
+
+        Err(String::from(match self {
+            PromptType::Bool => "The provided value is invalid. (yes|y|true|no|n|false)",
+            PromptType::Int => "The value should be an integer number",
+            PromptType::UInt => "The value should be a positive integer number",
+            PromptType::Float => "The value should be a number",
+            PromptType::UFloat => "The value should be a positive number",
+            PromptType::FilePathExisting => "The file does not exist",
+            PromptType::Date => "The value should be a date. (YYYY-MM-DD)",
+            PromptType::Time => "The value should be a time. (HH:MM)",
+            PromptType::DateTime => "The value should be a date and time. (YYYY-MM-DD HH:MM)",
+            _ => "The text should contain 1 character or more",
+        }))
+    }
 }
 
 #[derive(Debug)]
 pub enum PromptError {
     IO(io::Error),
     KeyboardInterrupt,
+    /// No answer could be read without a terminal, holds the prompt text
+    Unanswered(String),
+    /// Keys of the prompts left without an answer in strict unattended mode
+    MissingAnswers(Vec<String>),
     Custom(String),
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn validate() {
+        assert!(PromptType::Bool.validate("yes").is_ok());
+        assert!(PromptType::Int.validate("-3").is_ok());
+        assert!(PromptType::UInt.validate("-3").is_err());
+        assert!(PromptType::UFloat.validate("2.5").is_ok());
+        assert!(PromptType::UFloat.validate("-2.5").is_err());
+        assert!(PromptType::Date.validate("2024-02-29").is_ok());
+        assert!(PromptType::Time.validate("25:00").is_err());
+        assert!(PromptType::String.validate("").is_err());
+
+        assert_eq!(
+            PromptType::Float.validate("x"),
+            Err(String::from("The value should be a number"))
+        );
+        assert_eq!(
+            PromptType::FilePathExisting.validate("/no/such/file"),
+            Err(String::from("The file does not exist"))
+        );
+    }
+}
@@ -0,0 +1,764 @@
+use std::fmt::{self, Display, Formatter};
+use std::io::{Read, Write};
+use std::str::FromStr;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use termion::event::{Event, Key};
+use termion::input::Events;
+
+use crate::themes::FormatTheme;
+use crate::{
+    io_handl, is_resize, Frame, FrameWriter, MyFromStr, PromptBase, PromptError, PromptType,
+};
+
+const MONTHS: [&str; 12] = [
+    "January",
+    "February",
+    "March",
+    "April",
+    "May",
+    "June",
+    "July",
+    "August",
+    "September",
+    "October",
+    "November",
+    "December",
+];
+
+pub fn is_leap_year(year: i32) -> bool {
+    (year % 4 == 0 && year % 100 != 0) || year % 400 == 0
+}
+
+pub fn days_in_month(year: i32, month: u8) -> u8 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Date {
+    pub year: i32,
+    pub month: u8,
+    pub day: u8,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Time {
+    pub hour: u8,
+    pub minute: u8,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct DateTime {
+    pub date: Date,
+    pub time: Time,
+}
+
+impl Date {
+    pub fn new(year: i32, month: u8, day: u8) -> Option<Self> {
+        if !(1..=12).contains(&month) || day == 0 || day > days_in_month(year, month) {
+            return None;
+        }
+
+        Some(Date { year, month, day })
+    }
+
+    /// Days since 1970-01-01
+    pub fn to_days(self) -> i64 {
+        let month = self.month as i64;
+        let year = self.year as i64 - if month <= 2 { 1 } else { 0 };
+        let era = year.div_euclid(400);
+        let yoe = year - era * 400;
+        let doy = (153 * (month + if month > 2 { -3 } else { 9 }) + 2) / 5 + self.day as i64 - 1;
+        let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+
+        era * 146097 + doe - 719468
+    }
+
+    pub fn from_days(days: i64) -> Self {
+        let days = days + 719468;
+        let era = days.div_euclid(146097);
+        let doe = days - era * 146097;
+        let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+        let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+        let mp = (5 * doy + 2) / 153;
+        let day = (doy - (153 * mp + 2) / 5 + 1) as u8;
+        let month = if mp < 10 { mp + 3 } else { mp - 9 } as u8;
+        let year = (yoe + era * 400 + if month <= 2 { 1 } else { 0 }) as i32;
+
+        Date { year, month, day }
+    }
+
+    pub fn add_days(self, days: i64) -> Self {
+        Date::from_days(self.to_days() + days)
+    }
+
+    /// Move by whole months, keeping the day inside the new month
+    pub fn add_months(self, months: i32) -> Self {
+        let index = self.year * 12 + self.month as i32 - 1 + months;
+        let year = index.div_euclid(12);
+        let month = index.rem_euclid(12) as u8 + 1;
+
+        Date {
+            year,
+            month,
+            day: self.day.min(days_in_month(year, month)),
+        }
+    }
+
+    /// Day of the week, starting with Monday as 0
+    pub fn weekday(self) -> u8 {
+        (self.to_days() + 3).rem_euclid(7) as u8
+    }
+
+    /// Current date in UTC
+    pub fn today() -> Self {
+        DateTime::now().date
+    }
+}
+
+impl Time {
+    pub fn new(hour: u8, minute: u8) -> Option<Self> {
+        if hour > 23 || minute > 59 {
+            return None;
+        }
+
+        Some(Time { hour, minute })
+    }
+}
+
+impl DateTime {
+    /// Current date and time in UTC
+    pub fn now() -> Self {
+        let secs = match SystemTime::now().duration_since(UNIX_EPOCH) {
+            Ok(elapsed) => elapsed.as_secs() as i64,
+            Err(_) => 0,
+        };
+        let minutes = secs.rem_euclid(86400) / 60;
+
+        DateTime {
+            date: Date::from_days(secs.div_euclid(86400)),
+            time: Time {
+                hour: (minutes / 60) as u8,
+                minute: (minutes % 60) as u8,
+            },
+        }
+    }
+
+    /// Render with `%Y`, `%m`, `%d`, `%H`, `%M` and `%%` placeholders
+    pub fn format(&self, fmt: &str) -> String {
+        let mut out = String::new();
+        for part in parse_format(fmt) {
+            match part {
+                FormatPart::Literal(ch) => out.push(ch),
+                FormatPart::Segment(segment) => out.push_str(&segment.show(self)),
+            }
+        }
+
+        out
+    }
+}
+
+#[derive(Debug)]
+pub struct DateParseErr;
+
+impl DateParseErr {
+    pub fn get_err_message() -> &'static str {
+        "Provided date or time is invalid"
+    }
+}
+
+impl Display for DateParseErr {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        DateParseErr::get_err_message().fmt(f)
+    }
+}
+
+fn parse_num<N: FromStr>(s: &str) -> Result<N, DateParseErr> {
+    s.trim().parse().map_err(|_| DateParseErr)
+}
+
+impl FromStr for Date {
+    type Err = DateParseErr;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.trim().rsplitn(3, '-');
+        let day = parse_num(parts.next().ok_or(DateParseErr)?)?;
+        let month = parse_num(parts.next().ok_or(DateParseErr)?)?;
+        let year = parse_num(parts.next().ok_or(DateParseErr)?)?;
+
+        Date::new(year, month, day).ok_or(DateParseErr)
+    }
+}
+
+impl FromStr for Time {
+    type Err = DateParseErr;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (hour, minute) = s.trim().split_once(':').ok_or(DateParseErr)?;
+
+        Time::new(parse_num(hour)?, parse_num(minute)?).ok_or(DateParseErr)
+    }
+}
+
+impl FromStr for DateTime {
+    type Err = DateParseErr;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (date, time) = s.trim().split_once([' ', 'T']).ok_or(DateParseErr)?;
+
+        Ok(DateTime {
+            date: date.parse()?,
+            time: time.parse()?,
+        })
+    }
+}
+
+macro_rules! date_from_str {
+    ($($t:ty),+) => {
+        $(
+            impl MyFromStr for $t {
+                type Err_ = DateParseErr;
+
+                fn from_str(s: &str) -> Result<Self, Self::Err_> {
+                    FromStr::from_str(s)
+                }
+            }
+        )+
+    };
+}
+
+date_from_str!(Date, Time, DateTime);
+
+impl Display for Date {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+impl Display for Time {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{:02}:{:02}", self.hour, self.minute)
+    }
+}
+
+impl Display for DateTime {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.date, self.time)
+    }
+}
+
+/// Values the date prompts can return
+pub trait DateValue: Copy + Ord + Display + MyFromStr {
+    const PROMPT_TYPE: PromptType;
+    const FORMAT: &'static str;
+
+    fn to_date_time(self) -> DateTime;
+    fn from_date_time(value: DateTime) -> Self;
+}
+
+impl DateValue for Date {
+    const PROMPT_TYPE: PromptType = PromptType::Date;
+    const FORMAT: &'static str = "%Y-%m-%d";
+
+    fn to_date_time(self) -> DateTime {
+        DateTime {
+            date: self,
+            time: Time { hour: 0, minute: 0 },
+        }
+    }
+
+    fn from_date_time(value: DateTime) -> Self {
+        value.date
+    }
+}
+
+impl DateValue for Time {
+    const PROMPT_TYPE: PromptType = PromptType::Time;
+    const FORMAT: &'static str = "%H:%M";
+
+    fn to_date_time(self) -> DateTime {
+        DateTime {
+            date: Date::today(),
+            time: self,
+        }
+    }
+
+    fn from_date_time(value: DateTime) -> Self {
+        value.time
+    }
+}
+
+impl DateValue for DateTime {
+    const PROMPT_TYPE: PromptType = PromptType::DateTime;
+    const FORMAT: &'static str = "%Y-%m-%d %H:%M";
+
+    fn to_date_time(self) -> DateTime {
+        self
+    }
+
+    fn from_date_time(value: DateTime) -> Self {
+        value
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum DateSegment {
+    Year,
+    Month,
+    Day,
+    Hour,
+    Minute,
+}
+
+impl DateSegment {
+    fn width(self) -> usize {
+        match self {
+            DateSegment::Year => 4,
+            _ => 2,
+        }
+    }
+
+    fn show(self, value: &DateTime) -> String {
+        match self {
+            DateSegment::Year => format!("{:04}", value.date.year),
+            DateSegment::Month => format!("{:02}", value.date.month),
+            DateSegment::Day => format!("{:02}", value.date.day),
+            DateSegment::Hour => format!("{:02}", value.time.hour),
+            DateSegment::Minute => format!("{:02}", value.time.minute),
+        }
+    }
+
+    /// Move the segment by `delta`. Months carry into the year, the other
+    /// segments wrap inside their range.
+    fn step(self, value: DateTime, delta: i32) -> DateTime {
+        let mut value = value;
+        match self {
+            DateSegment::Year => value.date = value.date.add_months(delta * 12),
+            DateSegment::Month => value.date = value.date.add_months(delta),
+            DateSegment::Day => {
+                let max_day = days_in_month(value.date.year, value.date.month) as i32;
+                value.date.day = (value.date.day as i32 - 1 + delta).rem_euclid(max_day) as u8 + 1;
+            }
+            DateSegment::Hour => {
+                value.time.hour = (value.time.hour as i32 + delta).rem_euclid(24) as u8;
+            }
+            DateSegment::Minute => {
+                value.time.minute = (value.time.minute as i32 + delta).rem_euclid(60) as u8;
+            }
+        }
+
+        value
+    }
+
+    /// Replace the segment with a typed number, clamped to its range
+    fn set(self, value: DateTime, num: u32) -> DateTime {
+        let mut value = value;
+        match self {
+            DateSegment::Year => {
+                value.date.year = num as i32;
+                value.date.day = value
+                    .date
+                    .day
+                    .min(days_in_month(value.date.year, value.date.month));
+            }
+            DateSegment::Month => {
+                value.date.month = num.clamp(1, 12) as u8;
+                value.date.day = value
+                    .date
+                    .day
+                    .min(days_in_month(value.date.year, value.date.month));
+            }
+            DateSegment::Day => {
+                let max_day = days_in_month(value.date.year, value.date.month) as u32;
+                value.date.day = num.clamp(1, max_day) as u8;
+            }
+            DateSegment::Hour => value.time.hour = num.min(23) as u8,
+            DateSegment::Minute => value.time.minute = num.min(59) as u8,
+        }
+
+        value
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum FormatPart {
+    Literal(char),
+    Segment(DateSegment),
+}
+
+fn parse_format(fmt: &str) -> Vec<FormatPart> {
+    let mut parts = vec![];
+    let mut chars = fmt.chars();
+
+    while let Some(ch) = chars.next() {
+        if ch != '%' {
+            parts.push(FormatPart::Literal(ch));
+            continue;
+        }
+
+        parts.push(match chars.next() {
+            Some('Y') => FormatPart::Segment(DateSegment::Year),
+            Some('m') => FormatPart::Segment(DateSegment::Month),
+            Some('d') => FormatPart::Segment(DateSegment::Day),
+            Some('H') => FormatPart::Segment(DateSegment::Hour),
+            Some('M') => FormatPart::Segment(DateSegment::Minute),
+            Some(other) => FormatPart::Literal(other),
+            None => FormatPart::Literal('%'),
+        });
+    }
+
+    parts
+}
+
+#[derive(Debug)]
+pub struct DatePrompt<'a, T: FormatTheme, V: DateValue> {
+    pub base: &'a mut PromptBase,
+    pub theme: &'a T,
+    pub min: Option<V>,
+    pub max: Option<V>,
+    /// Display format, defaults to the ISO format of the value
+    pub format: Option<String>,
+    /// Show a month grid under the prompt
+    pub calendar: bool,
+}
+
+impl<'a, T: FormatTheme, V: DateValue> DatePrompt<'a, T, V> {
+    fn clamp(&self, value: DateTime) -> DateTime {
+        let mut value = V::from_date_time(value);
+        if let Some(min) = self.min {
+            value = value.max(min);
+        }
+        if let Some(max) = self.max {
+            value = value.min(max);
+        }
+
+        value.to_date_time()
+    }
+
+    fn in_range(&self, date: Date) -> bool {
+        let day = DateTime {
+            date,
+            time: Time { hour: 0, minute: 0 },
+        };
+        let last_minute = DateTime {
+            date,
+            time: Time {
+                hour: 23,
+                minute: 59,
+            },
+        };
+
+        self.min
+            .is_none_or(|min| V::from_date_time(last_minute) >= min)
+            && self.max.is_none_or(|max| V::from_date_time(day) <= max)
+    }
+
+    fn initial(&self) -> DateTime {
+        let value = match &self.base.default {
+            Some(default) => match <V as MyFromStr>::from_str(default) {
+                Ok(value) => value.to_date_time(),
+                Err(_) => DateTime::now(),
+            },
+            None => DateTime::now(),
+        };
+
+        self.clamp(value)
+    }
+
+    /// `value` written with the format, `active` segment highlighted when
+    /// given and every part left plain when `styled` is false
+    fn format_value(&self, value: &DateTime, active: Option<DateSegment>, styled: bool) -> String {
+        let fmt = self.format.as_deref().unwrap_or(V::FORMAT);
+        let mut out = String::new();
+
+        for part in parse_format(fmt) {
+            let (text, current) = match part {
+                FormatPart::Literal(ch) => (ch.to_string(), false),
+                FormatPart::Segment(segment) => (segment.show(value), Some(segment) == active),
+            };
+            if !styled {
+                out.push_str(&text);
+            } else if current {
+                out.push_str(&self.theme.format_highlight(&text));
+            } else {
+                out.push_str(&self.theme.format_input(&text));
+            }
+        }
+
+        out
+    }
+
+    fn calendar_lines(&self, date: Date, focused: bool) -> Vec<String> {
+        let first = Date { day: 1, ..date };
+        let title = format!("{} {}", MONTHS[date.month as usize - 1], date.year);
+        let mut lines = vec![
+            self.theme.format_title(&format!("{:^20}", title)),
+            self.theme.format_hint("Mo Tu We Th Fr Sa Su"),
+        ];
+
+        let mut line = "   ".repeat(first.weekday() as usize);
+        for day in 1..=days_in_month(date.year, date.month) {
+            let current = Date { day, ..date };
+            let cell = format!("{:>2}", day);
+            let cell = if day == date.day && focused {
+                self.theme.format_highlight(&cell)
+            } else if day == date.day {
+                self.theme.format_input(&cell)
+            } else if !self.in_range(current) {
+                self.theme.format_hint(&cell)
+            } else {
+                cell
+            };
+            line.push_str(&cell);
+
+            if current.weekday() == 6 {
+                lines.push(line);
+                line = String::new();
+            } else {
+                line.push(' ');
+            }
+        }
+        if !line.is_empty() {
+            lines.push(line);
+        }
+
+        lines
+    }
+
+    pub fn prompt<R: Read, W: Write>(
+        &mut self,
+        stdin: &mut Events<R>,
+        stdout: &mut W,
+        go_back: Option<bool>,
+    ) -> Result<V, PromptError> {
+        if let Some(answer) = self.base.resolve(self.theme, stdin, stdout, |line| {
+            // Only fails when the value does not parse
+            let value = <V as MyFromStr>::from_str(line)
+                .map_err(|_| V::PROMPT_TYPE.validate(line).unwrap_err())?;
+            if self.clamp(value.to_date_time()) != value.to_date_time() {
+                return Err(String::from("The value is out of the allowed range"));
+            }
+            Ok(value)
+        }) {
+            return answer;
+        }
+
+        io_handl!(self.base.start_line(stdout, go_back.unwrap_or(true)));
+
+        let fmt = self
+            .format
+            .clone()
+            .unwrap_or_else(|| String::from(V::FORMAT));
+        let segments: Vec<DateSegment> = parse_format(&fmt)
+            .into_iter()
+            .filter_map(|part| match part {
+                FormatPart::Segment(segment) => Some(segment),
+                FormatPart::Literal(_) => None,
+            })
+            .collect();
+        if segments.is_empty() {
+            return Err(PromptError::Custom(String::from(
+                "The date format should contain at least one segment",
+            )));
+        }
+
+        let has_date = segments
+            .iter()
+            .any(|s| matches!(s, DateSegment::Year | DateSegment::Month | DateSegment::Day));
+        let calendar = self.calendar && has_date;
+
+        let mut writer = FrameWriter::new();
+        let mut value = self.initial();
+        let mut active: usize = 0;
+        let mut typed = String::new();
+        // With the calendar shown the arrows move over the grid until Tab
+        // hands them back to the segments
+        let mut on_grid = calendar;
+
+        macro_rules! update {
+            ($active:expr) => {
+                let mut frame = Frame::new();
+                frame.push(format!(
+                    "{}{}",
+                    self.theme.format_prompt(self.base),
+                    self.format_value(&value, $active, true)
+                ));
+                frame.cursor_here(0);
+                if calendar {
+                    for line in self.calendar_lines(value.date, on_grid) {
+                        frame.push(line);
+                    }
+                }
+                self.base.add_help(self.theme, &mut frame);
+                io_handl!(writer.draw(stdout, &frame));
+            };
+        }
+
+        macro_rules! active_segment {
+            () => {
+                if on_grid {
+                    None
+                } else {
+                    Some(segments[active])
+                }
+            };
+        }
+
+        update!(active_segment!());
+
+        for c in stdin {
+            let evt = c.unwrap();
+            if is_resize(&evt) {
+                io_handl!(writer.redraw(stdout));
+                continue;
+            }
+            match evt {
+                Event::Key(Key::Ctrl('c') | Key::Esc) => {
+                    io_handl!(writer.finish_with(stdout, self.theme.format_cancelled(self.base)));
+                    return Err(PromptError::KeyboardInterrupt);
+                }
+                Event::Key(Key::Char('\n')) => {
+                    value = self.clamp(value);
+                    self.base.record(&V::from_date_time(value).to_string());
+
+                    io_handl!(writer.finish_with(
+                        stdout,
+                        self.base
+                            .answered_line(self.theme, &self.format_value(&value, None, false))
+                    ));
+                    return Ok(V::from_date_time(value));
+                }
+                Event::Key(Key::Char('\t')) if calendar => {
+                    on_grid = !on_grid;
+                    typed.clear();
+                }
+                Event::Key(Key::Left) if on_grid => {
+                    value.date = value.date.add_days(-1);
+                }
+                Event::Key(Key::Right) if on_grid => {
+                    value.date = value.date.add_days(1);
+                }
+                Event::Key(Key::Up) if on_grid => {
+                    value.date = value.date.add_days(-7);
+                }
+                Event::Key(Key::Down) if on_grid => {
+                    value.date = value.date.add_days(7);
+                }
+                Event::Key(Key::PageUp) if calendar => {
+                    value.date = value.date.add_months(-1);
+                }
+                Event::Key(Key::PageDown) if calendar => {
+                    value.date = value.date.add_months(1);
+                }
+                Event::Key(Key::Left) => {
+                    active = active.saturating_sub(1);
+                    typed.clear();
+                }
+                Event::Key(Key::Right | Key::Char('\t')) => {
+                    active = (active + 1).min(segments.len() - 1);
+                    typed.clear();
+                }
+                Event::Key(Key::Up) => {
+                    value = segments[active].step(value, 1);
+                    typed.clear();
+                }
+                Event::Key(Key::Down) => {
+                    value = segments[active].step(value, -1);
+                    typed.clear();
+                }
+                Event::Key(Key::Char(ch)) if ch.is_ascii_digit() && !on_grid => {
+                    let segment = segments[active];
+                    typed.push(ch);
+                    value = segment.set(value, typed.parse().unwrap_or(0));
+
+                    if typed.len() >= segment.width() {
+                        typed.clear();
+                        active = (active + 1).min(segments.len() - 1);
+                    }
+                }
+                _ => continue,
+            };
+
+            // A segment still being typed is not clamped, its first digits
+            // alone would be out of range
+            if typed.is_empty() {
+                value = self.clamp(value);
+            }
+            update!(active_segment!());
+        }
+
+        Ok(V::from_date_time(value))
+    }
+
+    pub fn prompt_handled<R: Read, W: Write>(
+        &mut self,
+        stdin: &mut Events<R>,
+        stdout: &mut W,
+        go_back: Option<bool>,
+    ) -> Result<V, PromptError> {
+        if self.base.prompt_type != V::PROMPT_TYPE {
+            return Err(PromptError::Custom(format!(
+                "Prompt struct only can be {:?}",
+                V::PROMPT_TYPE
+            )));
+        }
+
+        self.prompt(stdin, stdout, go_back)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{themes::DefaultTheme, TermRead};
+
+    #[test]
+    pub fn days() {
+        let date = Date::new(2024, 2, 29).unwrap();
+        assert_eq!(Date::from_days(date.to_days()), date);
+        assert_eq!(Date::from_days(0), Date::new(1970, 1, 1).unwrap());
+        assert_eq!(date.add_days(1), Date::new(2024, 3, 1).unwrap());
+        assert_eq!(date.add_months(12), Date::new(2025, 2, 28).unwrap());
+        assert_eq!(date.weekday(), 3);
+    }
+
+    #[test]
+    pub fn parse() {
+        let value: DateTime = MyFromStr::from_str("2024-05-01 13:45").unwrap();
+        assert_eq!(value.format("%d/%m/%Y %H.%M"), "01/05/2024 13.45");
+
+        assert!(<Date as MyFromStr>::from_str("2023-02-29").is_err());
+        assert!(<Time as MyFromStr>::from_str("24:00").is_err());
+    }
+
+    #[test]
+    pub fn typed_segments() {
+        let mut base = PromptBase {
+            default: Some(String::from("2025-01-10")),
+            ..PromptBase::test(PromptType::Date)
+        };
+        let mut prompt = DatePrompt {
+            base: &mut base,
+            theme: &DefaultTheme,
+            min: Date::new(2024, 6, 15),
+            max: None,
+            format: None,
+            calendar: false,
+        };
+        let mut out = Vec::new();
+
+        let mut stdin = (&b"2026\r"[..]).events();
+        let date = prompt.prompt(&mut stdin, &mut out, Some(false));
+        assert_eq!(date.unwrap(), Date::new(2026, 1, 10).unwrap());
+
+        // From the year to the month, then back into the last year
+        let mut stdin = (&b"\x1b[C\x1b[B\r"[..]).events();
+        let date = prompt.prompt(&mut stdin, &mut out, Some(false));
+        assert_eq!(date.unwrap(), Date::new(2024, 12, 10).unwrap());
+    }
+}
@@ -0,0 +1,272 @@
+use std::io::{self, Write};
+
+use termion::clear::{AfterCursor, CurrentLine, UntilNewline};
+use termion::cursor::{Hide, Left, Right, Show, Up};
+
+use crate::ansi;
+
+/// A full snapshot of what a prompt shows, one entry per terminal row.
+///
+/// The cursor is placed on `cursor_row`, `cursor_back` columns before the
+/// end of that row, the same way `Left(post_data.len())` is used by the
+/// single line prompts.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Frame {
+    pub lines: Vec<String>,
+    pub cursor_row: usize,
+    pub cursor_back: usize,
+}
+
+impl Frame {
+    pub fn new() -> Self {
+        Frame::default()
+    }
+
+    pub fn push<S: Into<String>>(&mut self, line: S) {
+        self.lines.push(line.into());
+    }
+
+    /// Put the cursor on the last pushed line
+    pub fn cursor_here(&mut self, back: usize) {
+        self.cursor_row = self.lines.len().saturating_sub(1);
+        self.cursor_back = back;
+    }
+}
+
+/// Rows a frame takes on the terminal once its lines are wrapped
+#[derive(Debug, Default, PartialEq)]
+struct Layout {
+    rows: Vec<String>,
+    cursor_row: usize,
+    cursor_col: usize,
+}
+
+impl Layout {
+    /// `width` is `None` when it is unknown, then lines are not wrapped
+    fn new(frame: &Frame, width: Option<usize>) -> Self {
+        let mut layout = Layout::default();
+        let cursor_line = frame.cursor_row.min(frame.lines.len().saturating_sub(1));
+
+        for (i, line) in frame.lines.iter().enumerate() {
+            let start = layout.rows.len();
+            let mut rows = match width {
+                Some(width) => ansi::wrap_hard(line, width),
+                None => vec![line.clone()],
+            };
+
+            if i == cursor_line {
+                let col = ansi::width(line).saturating_sub(frame.cursor_back);
+                let (row, col) = match width {
+                    Some(width) => (col / width, col % width),
+                    None => (0, col),
+                };
+                // A full last row leaves the cursor at the start of the next
+                if row >= rows.len() {
+                    rows.push(String::new());
+                }
+                layout.cursor_row = start + row;
+                layout.cursor_col = col;
+            }
+
+            layout.rows.extend(rows);
+        }
+
+        if layout.rows.is_empty() {
+            layout.rows.push(String::new());
+        }
+        layout
+    }
+}
+
+fn terminal_width() -> Option<usize> {
+    termion::terminal_size()
+        .ok()
+        .map(|(width, _)| width as usize)
+        .filter(|width| *width > 0)
+}
+
+/// Move the cursor `from` one row `to` another in the same frame. Going
+/// down uses newlines so the rows below are created when missing.
+fn move_rows(out: &mut String, from: usize, to: usize) {
+    if to < from {
+        out.push_str(&Up((from - to) as u16).to_string());
+    }
+    for _ in from..to {
+        out.push('\n');
+    }
+}
+
+/// Redraws frames in place, remembering the rows the last one used.
+/// Lines wider than the terminal are wrapped, and a frame drawn after a
+/// resize first finds where the terminal moved the last one.
+///
+/// Every frame is built in memory and written at once with the cursor
+/// hidden. Only the rows that changed are written, starting after the part
+/// they share with the previous frame.
+#[derive(Clone, Debug, Default)]
+pub struct FrameWriter {
+    rows: Vec<String>,
+    cursor_row: usize,
+    width: Option<usize>,
+    last: Frame,
+}
+
+impl FrameWriter {
+    pub fn new() -> Self {
+        FrameWriter::default()
+    }
+
+    pub fn draw<W: Write>(&mut self, stdout: &mut W, frame: &Frame) -> io::Result<()> {
+        let width = terminal_width();
+        let layout = Layout::new(frame, width);
+        let mut out = Hide.to_string();
+        let mut row_at;
+
+        if width != self.width && !self.rows.is_empty() {
+            // Terminals rewrap what is on screen when their width changes,
+            // so the old rows can not be trusted and all is drawn again
+            let up = Layout::new(&self.last, width).cursor_row;
+            move_rows(&mut out, up, 0);
+
+            for (i, row) in layout.rows.iter().enumerate() {
+                if i > 0 {
+                    out.push('\n');
+                }
+                out.push_str(&format!("{}{}{}", Left(999), CurrentLine, row));
+            }
+            out.push_str(AfterCursor.as_ref());
+            row_at = layout.rows.len() - 1;
+        } else {
+            row_at = self.cursor_row;
+
+            for (i, row) in layout.rows.iter().enumerate() {
+                let old = self.rows.get(i);
+                if old == Some(row) {
+                    continue;
+                }
+
+                move_rows(&mut out, row_at, i);
+                row_at = i;
+
+                let prefix = old
+                    .map(|old| ansi::common_prefix(old, row))
+                    .unwrap_or_default();
+                out.push_str(&Left(999).to_string());
+                if prefix.width > 0 {
+                    out.push_str(&Right(prefix.width as u16).to_string());
+                }
+                out.push_str(&prefix.style);
+                out.push_str(&row[prefix.len..]);
+
+                // A full row leaves the cursor on its last cell, clearing
+                // from there would erase it
+                if width.is_none_or(|width| ansi::width(row) < width) {
+                    out.push_str(UntilNewline.as_ref());
+                }
+            }
+
+            // Clear the rows left by a taller previous frame
+            if self.rows.len() > layout.rows.len() {
+                move_rows(&mut out, row_at, layout.rows.len());
+                row_at = layout.rows.len();
+                out.push_str(&format!("{}{}", Left(999), AfterCursor));
+            }
+        }
+
+        move_rows(&mut out, row_at, layout.cursor_row);
+        out.push_str(&Left(999).to_string());
+        if layout.cursor_col > 0 {
+            out.push_str(&Right(layout.cursor_col as u16).to_string());
+        }
+        out.push_str(Show.as_ref());
+
+        self.rows = layout.rows;
+        self.cursor_row = layout.cursor_row;
+        self.width = width;
+        self.last = frame.clone();
+
+        stdout.write_all(out.as_bytes())?;
+        stdout.flush()
+    }
+
+    /// Draw the last frame again, e.g. after the terminal was resized
+    pub fn redraw<W: Write>(&mut self, stdout: &mut W) -> io::Result<()> {
+        let frame = self.last.clone();
+        self.draw(stdout, &frame)
+    }
+
+    /// Leave the cursor on a fresh line below the last frame
+    pub fn finish<W: Write>(&mut self, stdout: &mut W) -> io::Result<()> {
+        for _ in self.cursor_row..self.rows.len().max(1) {
+            writeln!(stdout)?;
+        }
+        write!(stdout, "{}", Left(999))?;
+
+        *self = FrameWriter::default();
+        stdout.flush()
+    }
+
+    /// Replace the frame with `line` and finish, or clear it and leave the
+    /// cursor where it started when there is no line
+    pub fn finish_with<W: Write, L: Into<Option<String>>>(
+        &mut self,
+        stdout: &mut W,
+        line: L,
+    ) -> io::Result<()> {
+        let mut frame = Frame::new();
+        if let Some(line) = line.into() {
+            frame.push(line);
+            self.draw(stdout, &frame)?;
+            return self.finish(stdout);
+        }
+
+        self.draw(stdout, &frame)?;
+        *self = FrameWriter::default();
+        stdout.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn wrapped_layout() {
+        let mut frame = Frame::new();
+        frame.push("? Name > abcdefgh");
+        frame.cursor_here(2);
+        frame.push("error");
+
+        let layout = Layout::new(&frame, Some(8));
+        assert_eq!(layout.rows, vec!["? Name >", " abcdefg", "h", "error"]);
+        assert_eq!((layout.cursor_row, layout.cursor_col), (1, 7));
+
+        let mut frame = Frame::new();
+        frame.push("? Name > abcdefg");
+        frame.cursor_here(0);
+        let layout = Layout::new(&frame, Some(8));
+        assert_eq!(layout.rows, vec!["? Name >", " abcdefg", ""]);
+        assert_eq!((layout.cursor_row, layout.cursor_col), (2, 0));
+    }
+
+    #[test]
+    pub fn only_changes_written() {
+        let mut out = Vec::new();
+        let mut writer = FrameWriter::new();
+
+        let mut frame = Frame::new();
+        frame.push("? Name > ab");
+        frame.cursor_here(0);
+        frame.push("hint");
+        writer.draw(&mut out, &frame).unwrap();
+
+        frame.lines[0].push('c');
+        out.clear();
+        writer.draw(&mut out, &frame).unwrap();
+
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.contains('c'));
+        assert!(!out.contains("Name"));
+        assert!(!out.contains("hint"));
+    }
+}
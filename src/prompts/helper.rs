@@ -1,16 +1,116 @@
 use std::io::{self, Write};
 
+use termion::cursor::{DetectCursorPos, Goto};
+use termion::event::{Event, Key};
+
 use crate::themes::FormatTheme;
-use crate::PromptType;
+use crate::{record_answer, Frame, PromptType};
+
+/// Arrow pressed together with shift. Termion has no key for these and
+/// reports the raw `CSI 1;2 X` sequence as unsupported.
+pub fn shift_arrow(evt: &Event) -> Option<Key> {
+    match evt {
+        Event::Unsupported(seq) => match seq.as_slice() {
+            b"\x1b[1;2A" => Some(Key::Up),
+            b"\x1b[1;2B" => Some(Key::Down),
+            b"\x1b[1;2C" => Some(Key::Right),
+            b"\x1b[1;2D" => Some(Key::Left),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// `text` with `\` and each of `separators` escaped with a `\`, so values
+/// joined with them can be told apart again by `split_unescaped`
+pub fn escape_separators(text: &str, separators: &[char]) -> String {
+    let mut out = String::new();
+    for ch in text.chars() {
+        if ch == '\\' || separators.contains(&ch) {
+            out.push('\\');
+        }
+        out.push(ch);
+    }
+
+    out
+}
+
+/// Parts of `line` between the `separator`s that are not escaped, still
+/// escaped themselves
+pub fn split_unescaped(line: &str, separator: char) -> Vec<&str> {
+    let mut parts = vec![];
+    let mut start = 0;
+    let mut escaped = false;
+
+    for (i, ch) in line.char_indices() {
+        if escaped {
+            escaped = false;
+        } else if ch == '\\' {
+            escaped = true;
+        } else if ch == separator {
+            parts.push(&line[start..i]);
+            start = i + ch.len_utf8();
+        }
+    }
+    parts.push(&line[start..]);
+
+    parts
+}
+
+/// `text` without the `\` that `escape_separators` put before `\` and each
+/// of `separators`. Other backslashes, like the ones of a Windows path, are
+/// kept.
+pub fn unescape(text: &str, separators: &[char]) -> String {
+    let mut out = String::new();
+    let mut chars = text.chars().peekable();
+
+    while let Some(ch) = chars.next() {
+        match (ch, chars.peek()) {
+            ('\\', Some(&next)) if next == '\\' || separators.contains(&next) => {
+                out.push(next);
+                chars.next();
+            }
+            _ => out.push(ch),
+        }
+    }
+
+    out
+}
+
+/// One `*` per character of `password`
+pub fn mask_password(password: &str) -> String {
+    "*".repeat(password.chars().count())
+}
+
+/// What is left on screen once a prompt is answered
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum FinishMode {
+    /// The theme's answered line, see `FormatTheme::format_answered`
+    #[default]
+    Summary,
+    /// The prompt as it was while editing
+    Keep,
+    /// Nothing, the next output takes the prompt's place
+    Clear,
+}
 
 #[derive(Clone, Debug)]
 pub struct PromptBase {
+    /// Stable name the answer is looked up with in `Answers`
+    pub key: Option<String>,
     pub prefix: String,
     pub text: String,
     pub default: Option<String>,
+    /// Loaded into the input to be edited, the cursor starts at its end
+    pub initial: Option<String>,
+    /// Shown in the empty input, it is never submitted
+    pub placeholder: Option<String>,
     pub extra: Option<String>,
+    /// Line shown under the prompt, such as `Use ↑/↓ to choose`
+    pub help: Option<String>,
     pub line: Option<u16>,
     pub prompt_type: PromptType,
+    pub finish: FinishMode,
 }
 
 impl PromptBase {
@@ -35,4 +135,235 @@ impl PromptBase {
         stdout.flush()?;
         Ok(())
     }
+
+    /// Placeholder for the empty input, the cursor goes before it. Empty
+    /// when there is none.
+    pub fn format_placeholder<T: FormatTheme>(&self, theme: &T) -> String {
+        match &self.placeholder {
+            Some(placeholder) => theme.format_placeholder(placeholder),
+            None => String::new(),
+        }
+    }
+
+    /// Put the help line under the first row of `frame`
+    pub fn add_help<T: FormatTheme>(&self, theme: &T, frame: &mut Frame) {
+        if let Some(help) = &self.help {
+            frame
+                .lines
+                .insert(1.min(frame.lines.len()), theme.format_hint(help));
+            if frame.cursor_row >= 1 {
+                frame.cursor_row += 1;
+            }
+        }
+    }
+
+    /// Line to leave once `answer` is given, `None` when it is cleared.
+    /// Passwords are masked.
+    pub fn answered_line<T: FormatTheme>(&self, theme: &T, answer: &str) -> Option<String> {
+        let answer = match self.prompt_type {
+            PromptType::Password => mask_password(answer),
+            _ => String::from(answer),
+        };
+
+        match self.finish {
+            FinishMode::Summary => Some(theme.format_answered(self, &answer)),
+            FinishMode::Keep => Some(format!(
+                "{}{}",
+                theme.format_prompt(self),
+                theme.format_input(&answer)
+            )),
+            FinishMode::Clear => None,
+        }
+    }
+
+    /// Keep `answer` in the session being recorded, written the way it
+    /// would be typed so it can be used as an answer again. Passwords are
+    /// never kept.
+    pub fn record(&self, answer: &str) {
+        if self.prompt_type == PromptType::Password {
+            return;
+        }
+        if let Some(key) = &self.key {
+            record_answer(key, answer);
+        }
+    }
+
+    /// Remember the line where the prompt starts, or go back to it when
+    /// the same prompt is asked again with `go_back`. Without it the prompt
+    /// is drawn where the cursor is: the frame is redrawn from the cursor
+    /// and finishes below itself, so jumping back every time would draw
+    /// over whatever was printed after the first ask.
+    pub fn start_line<W: Write>(&mut self, stdout: &mut W, go_back: bool) -> io::Result<()> {
+        match self.line {
+            None => {
+                let (_, y) = DetectCursorPos::cursor_pos(stdout)?;
+                self.line = Option::Some(y);
+            }
+            Some(line) => {
+                if go_back {
+                    write!(stdout, "{}", Goto(1, line))?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+impl PromptBase {
+    /// Prompt for tests, already on its line so the terminal is never asked
+    /// where the cursor is
+    pub fn test(prompt_type: PromptType) -> Self {
+        PromptBase {
+            key: None,
+            prefix: String::from("?"),
+            text: String::from("Value"),
+            default: None,
+            initial: None,
+            placeholder: None,
+            extra: None,
+            help: None,
+            line: Some(1),
+            prompt_type,
+            finish: FinishMode::Summary,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        ansi, themes::DefaultTheme, FrameWriter, Mask, MaskPrompt, MultilinePrompt, NumberPrompt,
+        PromptError, TermRead, TextPrompt,
+    };
+    use termion::cursor::{Left, Right, Show, Up};
+
+    #[test]
+    pub fn escaping() {
+        let cell = escape_separators("a;b,c\\d", &[',', ';']);
+        assert_eq!(cell, "a\\;b\\,c\\\\d");
+        assert_eq!(split_unescaped(&format!("{},x", cell), ','), [&cell, "x"]);
+        assert_eq!(unescape(&cell, &[',', ';']), "a;b,c\\d");
+
+        // Only the given separators lose their backslash
+        assert_eq!(unescape("C:\\dir\\;x\\,y", &[',']), "C:\\dir\\;x,y");
+    }
+
+    #[test]
+    pub fn answered_line() {
+        let mut base = PromptBase {
+            text: String::from("Password"),
+            line: None,
+            ..PromptBase::test(PromptType::Password)
+        };
+
+        let line = base.answered_line(&DefaultTheme, "hunter2").unwrap();
+        assert_eq!(ansi::strip(&line), "✔ Password *******");
+        assert!(!line.contains("\x1b[999D"));
+
+        base.finish = FinishMode::Keep;
+        let line = base.answered_line(&DefaultTheme, "hunter2").unwrap();
+        assert!(ansi::strip(&line).ends_with("*******"));
+
+        base.finish = FinishMode::Clear;
+        assert_eq!(base.answered_line(&DefaultTheme, "hunter2"), None);
+    }
+
+    #[test]
+    pub fn placeholder_and_help() {
+        let mut base = PromptBase {
+            placeholder: Some(String::from("Jane Doe")),
+            help: Some(String::from("Shown on your profile")),
+            ..PromptBase::test(PromptType::String)
+        };
+
+        // The cursor stays before the placeholder and moves down with the
+        // row it is on when the help line goes in above it
+        let mut frame = Frame::new();
+        frame.push("? Notes");
+        frame.push(format!("> {}", base.format_placeholder(&DefaultTheme)));
+        frame.cursor_here(8);
+        frame.push("error");
+        base.add_help(&DefaultTheme, &mut frame);
+        assert_eq!(ansi::strip(&frame.lines[1]), "Shown on your profile");
+        assert_eq!(frame.cursor_row, 2);
+
+        let mut out = Vec::new();
+        FrameWriter::new().draw(&mut out, &frame).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.ends_with(&format!("{}{}{}{}", Up(1), Left(999), Right(2), Show)));
+
+        let mut prompt = TextPrompt {
+            base: &mut base,
+            theme: &DefaultTheme,
+        };
+        let mut stdin = (&b"\r"[..]).events();
+        let answer = prompt.prompt(&mut stdin, &mut Vec::new(), Some(false));
+        assert_eq!(answer.unwrap(), "");
+    }
+
+    #[test]
+    pub fn initial_at_end() {
+        let base = |prompt_type: PromptType, initial: &str| PromptBase {
+            initial: Some(String::from(initial)),
+            ..PromptBase::test(prompt_type)
+        };
+
+        let mut text = base(PromptType::String, "ab");
+        let answer = TextPrompt {
+            base: &mut text,
+            theme: &DefaultTheme,
+        }
+        .prompt(&mut (&b"c\r"[..]).events(), &mut Vec::new(), Some(false));
+        assert_eq!(answer.unwrap(), "abc");
+
+        let mut number = base(PromptType::Float, "-1.5");
+        let answer = NumberPrompt {
+            base: &mut number,
+            theme: &DefaultTheme,
+        }
+        .prompt(&mut (&b"2\r"[..]).events(), &mut Vec::new(), Some(false));
+        assert_eq!(answer.unwrap(), "-1.52");
+
+        let mut mask = base(PromptType::String, "12");
+        let answer = MaskPrompt {
+            base: &mut mask,
+            theme: &DefaultTheme,
+            mask: Mask::parse("##-##"),
+            raw: false,
+        }
+        .prompt(&mut (&b"34\r"[..]).events(), &mut Vec::new(), Some(false));
+        assert_eq!(answer.unwrap(), "12-34");
+
+        let mut notes = base(PromptType::MultiLine, "a\nb");
+        let answer = MultilinePrompt {
+            base: &mut notes,
+            theme: &DefaultTheme,
+            line_numbers: false,
+            editor: false,
+        }
+        .prompt(&mut (&b"c\x04"[..]).events(), &mut Vec::new(), Some(false));
+        assert_eq!(answer.unwrap(), "a\nbc");
+
+        // Characters the prompt would not take are an error, not dropped
+        let mut number = base(PromptType::UInt, "4x2");
+        let answer = NumberPrompt {
+            base: &mut number,
+            theme: &DefaultTheme,
+        }
+        .prompt(&mut (&b"\r"[..]).events(), &mut Vec::new(), Some(false));
+        assert!(matches!(answer, Err(PromptError::Custom(_))));
+
+        let mut mask = base(PromptType::String, "1a");
+        let answer = MaskPrompt {
+            base: &mut mask,
+            theme: &DefaultTheme,
+            mask: Mask::parse("##-##"),
+            raw: false,
+        }
+        .prompt(&mut (&b"\r"[..]).events(), &mut Vec::new(), Some(false));
+        assert!(matches!(answer, Err(PromptError::Custom(_))));
+    }
 }
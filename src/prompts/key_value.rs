@@ -0,0 +1,457 @@
+use std::io::{Read, Write};
+
+use regex::Regex;
+use termion::event::{Event, Key};
+use termion::input::Events;
+
+use crate::themes::FormatTheme;
+use crate::{
+    ansi, escape_separators, forget_answer, io_handl, is_resize, split_unescaped, unescape, Frame,
+    FrameWriter, LineEditor, ListView, PromptBase, PromptError, PromptType, RedactInput,
+};
+
+/// Glob match where `*` stands for any run of characters, ignoring case
+pub fn glob_match(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.to_lowercase().chars().collect();
+    let text: Vec<char> = text.to_lowercase().chars().collect();
+
+    // Positions to retry from after the last `*`
+    let (mut p, mut t) = (0, 0);
+    let mut star: Option<(usize, usize)> = None;
+
+    while t < text.len() {
+        if p < pattern.len() && pattern[p] == '*' {
+            star = Some((p, t));
+            p += 1;
+        } else if p < pattern.len() && pattern[p] == text[t] {
+            p += 1;
+            t += 1;
+        } else if let Some((sp, st)) = star {
+            p = sp + 1;
+            t = st + 1;
+            star = Some((sp, st + 1));
+        } else {
+            return false;
+        }
+    }
+
+    pattern[p..].iter().all(|ch| *ch == '*')
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Field {
+    Key,
+    Value,
+}
+
+/// Edit an ordered list of `KEY = value` entries, such as `.env` files or
+/// HTTP headers
+#[derive(Debug)]
+pub struct KeyValuePrompt<'a, T: FormatTheme> {
+    pub base: &'a mut PromptBase,
+    pub theme: &'a T,
+    /// Entries the prompt starts with
+    pub entries: Vec<(String, String)>,
+    /// Keys must match it when set, otherwise any non empty key is valid
+    pub key_pattern: Option<Regex>,
+    /// Globs for keys whose values are masked, such as `*_TOKEN`
+    pub secret_keys: Vec<String>,
+    /// Rows shown at once, `0` shows every row
+    pub page_size: usize,
+}
+
+impl<'a, T: FormatTheme> KeyValuePrompt<'a, T> {
+    pub fn is_secret(&self, key: &str) -> bool {
+        self.secret_keys.iter().any(|glob| glob_match(glob, key))
+    }
+
+    fn validate_key(
+        &self,
+        entries: &[(String, String)],
+        idx: usize,
+        key: &str,
+    ) -> Result<(), String> {
+        if key.is_empty() {
+            return Err(String::from("The key should contain 1 character or more"));
+        }
+
+        if let Some(pattern) = &self.key_pattern {
+            if !pattern.is_match(key) {
+                return Err(format!("The key should match {}", pattern.as_str()));
+            }
+        }
+
+        let taken = entries
+            .iter()
+            .enumerate()
+            .any(|(i, (other, _))| i != idx && other == key);
+        if taken {
+            return Err(format!("The key {} is already defined", key));
+        }
+
+        Ok(())
+    }
+
+    /// `KEY=VALUE` entries separated by commas, empty keeps `entries`. A
+    /// `\` escapes a comma or `=` in a key or value.
+    fn parse_entries(&self, line: &str) -> Result<Vec<(String, String)>, String> {
+        if line.trim().is_empty() {
+            return Ok(self.entries.clone());
+        }
+
+        let mut entries: Vec<(String, String)> = vec![];
+        for entry in split_unescaped(line, ',') {
+            let key = split_unescaped(entry, '=')[0];
+            let value = entry
+                .get(key.len() + 1..)
+                .ok_or_else(|| String::from("Entries should be written as KEY=VALUE"))?;
+            let key = unescape(key.trim(), &[',', '=']);
+            self.validate_key(&entries, entries.len(), &key)?;
+            entries.push((key, unescape(value.trim(), &[',', '='])));
+        }
+
+        Ok(entries)
+    }
+
+    /// Keep `entries` in the session being recorded. Nothing is kept when
+    /// one of them is a secret, so a replay has to take the answer from
+    /// `Answers` instead of going on without the secret.
+    fn record(&self, entries: &[(String, String)]) {
+        if entries.iter().any(|(key, _)| self.is_secret(key)) {
+            if let Some(key) = &self.base.key {
+                forget_answer(key);
+            }
+            return;
+        }
+
+        let pairs: Vec<String> = entries
+            .iter()
+            .map(|(key, value)| {
+                format!(
+                    "{}={}",
+                    escape_separators(key, &[',', '=']),
+                    escape_separators(value, &[',', '='])
+                )
+            })
+            .collect();
+        self.base.record(&pairs.join(", "));
+    }
+
+    fn mask(&self, key: &str, value: &str) -> String {
+        if self.is_secret(key) {
+            self.theme
+                .glyphs()
+                .secret
+                .repeat(ansi::width(value).min(12))
+        } else {
+            String::from(value)
+        }
+    }
+
+    /// One bullet per column, for a secret being typed where the cursor has
+    /// to line up with what is shown
+    fn mask_chars(&self, text: &str) -> String {
+        self.theme.glyphs().secret.repeat(ansi::width(text))
+    }
+
+    /// Entries in the order they are shown. Keys are unique, so the result
+    /// works as an ordered map; it is a `Vec` because std has no map that
+    /// keeps insertion order.
+    pub fn prompt<R: Read, W: Write>(
+        &mut self,
+        stdin: &mut Events<R>,
+        stdout: &mut W,
+        go_back: Option<bool>,
+    ) -> Result<Vec<(String, String)>, PromptError> {
+        // Keys and values are read by the same input, so everything typed
+        // is kept out of a recording once some values are secret
+        let _redact = (!self.secret_keys.is_empty()).then(RedactInput::new);
+        if let Some(answer) = self
+            .base
+            .resolve(self.theme, stdin, stdout, |line| self.parse_entries(line))
+        {
+            if let Ok(entries) = &answer {
+                self.record(entries);
+            }
+            return answer;
+        }
+
+        io_handl!(self.base.start_line(stdout, go_back.unwrap_or(true)));
+
+        let mut entries = self.entries.clone();
+        let mut writer = FrameWriter::new();
+        let mut view = ListView::new(self.page_size);
+        let mut editing: Option<(Field, LineEditor)> = None;
+        // Entry added with Ctrl+N whose key is not confirmed yet
+        let mut adding = false;
+        let mut err: Option<String> = None;
+
+        macro_rules! update {
+            () => {
+                let key_width = entries
+                    .iter()
+                    .map(|(key, _)| ansi::width(key))
+                    .chain(match &editing {
+                        Some((Field::Key, editor)) => Some(ansi::width(&editor.value())),
+                        _ => None,
+                    })
+                    .max()
+                    .unwrap_or(0);
+
+                let mut frame = Frame::new();
+                frame.push(format!(
+                    "{}{}",
+                    self.theme.format_prompt(self.base),
+                    self.theme.format_hint(
+                        "(enter to edit, ctrl+r/n/x to rename/add/remove, ctrl+d to submit)"
+                    )
+                ));
+
+                let mut back = 0;
+                let lines: Vec<String> = entries
+                    .iter()
+                    .enumerate()
+                    .map(|(i, (key, value))| {
+                        let current = i == view.cursor;
+                        let (key, pad, value) = match &editing {
+                            Some((Field::Key, editor)) if current => {
+                                let shown = self.mask(&editor.value(), value);
+                                let pad = key_width - ansi::width(&editor.value());
+                                back = editor.cursor_back() + pad + 3 + ansi::width(&shown);
+                                (self.theme.format_input(&editor.value()), pad, shown)
+                            }
+                            Some((Field::Value, editor)) if current => {
+                                let pad = key_width - ansi::width(key);
+                                let shown = if self.is_secret(key) {
+                                    back = ansi::width(&self.mask_chars(&editor.post_data));
+                                    self.mask_chars(&editor.value())
+                                } else {
+                                    back = editor.cursor_back();
+                                    editor.value()
+                                };
+                                (
+                                    self.theme.format_label(key),
+                                    pad,
+                                    self.theme.format_input(&shown),
+                                )
+                            }
+                            _ => {
+                                let pad = key_width - ansi::width(key);
+                                (self.theme.format_label(key), pad, self.mask(key, value))
+                            }
+                        };
+
+                        format!(
+                            "{}{} {} {}",
+                            key,
+                            " ".repeat(pad),
+                            self.theme.format_hint("="),
+                            value
+                        )
+                    })
+                    .collect();
+
+                if lines.is_empty() {
+                    frame.push(self.theme.format_hint("  (no entries, ctrl+n to add one)"));
+                }
+                for line in view.render(self.theme, &lines) {
+                    frame.push(line);
+                }
+
+                if editing.is_some() {
+                    frame.cursor_row = 1 + view.cursor - view.offset;
+                    frame.cursor_back = back;
+                }
+
+                if let Some(err) = &err {
+                    frame.push(self.theme.format_error(err));
+                }
+                self.base.add_help(self.theme, &mut frame);
+                io_handl!(writer.draw(stdout, &frame));
+            };
+        }
+
+        update!();
+
+        for c in stdin {
+            let evt = c.unwrap();
+            if is_resize(&evt) {
+                io_handl!(writer.redraw(stdout));
+                continue;
+            }
+            let len = entries.len();
+
+            if let Event::Key(Key::Ctrl('c')) = evt {
+                io_handl!(writer.finish_with(stdout, self.theme.format_cancelled(self.base)));
+                return Err(PromptError::KeyboardInterrupt);
+            }
+
+            if let Some((field, editor)) = &mut editing {
+                let idx = view.cursor;
+                match evt {
+                    Event::Key(Key::Esc) => {
+                        if adding {
+                            entries.remove(idx);
+                            view.scroll(entries.len());
+                            adding = false;
+                        }
+                        editing = None;
+                        err = None;
+                    }
+                    Event::Key(Key::Char('\n')) if *field == Field::Key => {
+                        let key = editor.value();
+                        match self.validate_key(&entries, idx, &key) {
+                            Ok(()) => {
+                                entries[idx].0 = key;
+                                err = None;
+                                editing = if adding {
+                                    adding = false;
+                                    Some((Field::Value, LineEditor::new()))
+                                } else {
+                                    None
+                                };
+                            }
+                            Err(msg) => err = Some(msg),
+                        }
+                    }
+                    Event::Key(Key::Char('\n')) => {
+                        // A secret starts empty, leaving it empty keeps it
+                        if !(editor.is_empty() && self.is_secret(&entries[idx].0)) {
+                            entries[idx].1 = editor.value();
+                        }
+                        editing = None;
+                        err = None;
+                    }
+                    Event::Key(key) => {
+                        if !editor.handle(&key) {
+                            continue;
+                        }
+                    }
+                    _ => continue,
+                }
+
+                update!();
+                continue;
+            }
+
+            match evt {
+                Event::Key(Key::Esc) => {
+                    io_handl!(writer.finish_with(stdout, self.theme.format_cancelled(self.base)));
+                    return Err(PromptError::KeyboardInterrupt);
+                }
+                Event::Key(Key::Ctrl('d') | Key::Alt('\r') | Key::Alt('\n')) => {
+                    self.record(&entries);
+                    io_handl!(writer.finish_with(
+                        stdout,
+                        self.base
+                            .answered_line(self.theme, &format!("{} entries", entries.len()))
+                    ));
+                    return Ok(entries);
+                }
+                Event::Key(Key::Ctrl('n') | Key::Insert) => {
+                    let at = if len == 0 { 0 } else { view.cursor + 1 };
+                    entries.insert(at, (String::new(), String::new()));
+                    view.select(at, entries.len());
+                    editing = Some((Field::Key, LineEditor::new()));
+                    adding = true;
+                }
+                Event::Key(Key::Ctrl('x') | Key::Delete) if len > 0 => {
+                    entries.remove(view.cursor);
+                    view.scroll(entries.len());
+                }
+                Event::Key(Key::Ctrl('r')) if len > 0 => {
+                    let key = entries[view.cursor].0.clone();
+                    editing = Some((Field::Key, LineEditor::with_value(key)));
+                }
+                Event::Key(Key::Char('\n')) if len > 0 => {
+                    // Secrets start empty instead of showing the old value
+                    let (key, value) = &entries[view.cursor];
+                    let editor = if self.is_secret(key) {
+                        LineEditor::new()
+                    } else {
+                        LineEditor::with_value(value.clone())
+                    };
+                    editing = Some((Field::Value, editor));
+                }
+                Event::Key(Key::Up) => view.up(len),
+                Event::Key(Key::Down) => view.down(len),
+                Event::Key(Key::PageUp) => view.page_up(len),
+                Event::Key(Key::PageDown) => view.page_down(len),
+                Event::Key(Key::Home) => view.home(len),
+                Event::Key(Key::End) => view.end(len),
+                _ => continue,
+            };
+
+            err = None;
+            update!();
+        }
+
+        Ok(entries)
+    }
+
+    pub fn prompt_handled<R: Read, W: Write>(
+        &mut self,
+        stdin: &mut Events<R>,
+        stdout: &mut W,
+        go_back: Option<bool>,
+    ) -> Result<Vec<(String, String)>, PromptError> {
+        match &self.base.prompt_type {
+            PromptType::KeyValue => {}
+            _ => {
+                return Err(PromptError::Custom(String::from(
+                    "Prompt struct only can be KeyValue",
+                )));
+            }
+        };
+
+        self.prompt(stdin, stdout, go_back)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{lock_globals, themes::DefaultTheme, TermRead};
+
+    #[test]
+    pub fn secret_globs() {
+        assert!(glob_match("*_TOKEN", "GITHUB_TOKEN"));
+        assert!(glob_match("*_password", "DB_PASSWORD"));
+        assert!(glob_match("API_*_KEY", "API_STRIPE_KEY"));
+        assert!(!glob_match("*_TOKEN", "TOKEN_FILE"));
+        assert!(!glob_match("*_TOKEN", "GITHUB_TOKENS"));
+    }
+
+    #[test]
+    pub fn secret_editing() {
+        let mut base = PromptBase::test(PromptType::KeyValue);
+        let mut prompt = KeyValuePrompt {
+            base: &mut base,
+            theme: &DefaultTheme,
+            entries: vec![(String::from("API_TOKEN"), String::from("old"))],
+            key_pattern: None,
+            secret_keys: vec![String::from("*_TOKEN")],
+            page_size: 0,
+        };
+        // Secret keys redact the session another test may be recording
+        let _lock = lock_globals();
+        let mut stdin = (&b"\rs3cr\x1b[D\r\x04"[..]).events();
+        let mut out = Vec::new();
+
+        let entries = prompt.prompt(&mut stdin, &mut out, Some(false)).unwrap();
+        assert_eq!(entries[0].1, "s3cr");
+
+        prompt.entries = entries;
+        let mut stdin = (&b"\r\r\x04"[..]).events();
+        let entries = prompt
+            .prompt(&mut stdin, &mut Vec::new(), Some(false))
+            .unwrap();
+        assert_eq!(entries[0].1, "s3cr");
+
+        let out = String::from_utf8(out).unwrap();
+        // "old" is shown as 3 bullets, every typed character adds one
+        assert!(out.matches('•').count() > 3);
+        assert!(!out.contains("s3c"));
+        assert!(!out.contains("old"));
+    }
+}
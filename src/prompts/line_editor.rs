@@ -0,0 +1,134 @@
+use termion::event::Key;
+
+use crate::ansi;
+
+/// A single line of text split at the cursor, shared by every prompt that
+/// edits text in place
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct LineEditor {
+    pub pre_data: String,
+    pub post_data: String,
+}
+
+impl LineEditor {
+    pub fn new() -> Self {
+        LineEditor::default()
+    }
+
+    /// Editor holding `value` with the cursor at the end
+    pub fn with_value<S: Into<String>>(value: S) -> Self {
+        LineEditor {
+            pre_data: value.into(),
+            post_data: String::new(),
+        }
+    }
+
+    pub fn value(&self) -> String {
+        format!("{}{}", self.pre_data, self.post_data)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pre_data.is_empty() && self.post_data.is_empty()
+    }
+
+    /// Columns between the cursor and the end of the line
+    pub fn cursor_back(&self) -> usize {
+        ansi::width(&self.post_data)
+    }
+
+    pub fn set<S: Into<String>>(&mut self, value: S) {
+        self.pre_data = value.into();
+        self.post_data = String::new();
+    }
+
+    pub fn clear(&mut self) {
+        self.set(String::new());
+    }
+
+    /// Apply an editing key, returns `false` when nothing changed
+    pub fn handle(&mut self, key: &Key) -> bool {
+        match key {
+            Key::Char('\n') => false,
+            Key::Char(ch) => {
+                self.pre_data.push(*ch);
+                true
+            }
+            Key::Left => match self.pre_data.pop() {
+                Option::None => false,
+                Option::Some(ch) => {
+                    self.post_data.insert(0, ch);
+                    true
+                }
+            },
+            Key::Right => {
+                let mut chars = self.post_data.chars();
+                match chars.next() {
+                    Option::None => false,
+                    Option::Some(ch) => {
+                        self.pre_data.push(ch);
+                        self.post_data = chars.collect();
+                        true
+                    }
+                }
+            }
+            Key::Backspace => self.pre_data.pop().is_some(),
+            Key::Delete => {
+                let mut chars = self.post_data.chars();
+                match chars.next() {
+                    Option::None => false,
+                    Option::Some(_) => {
+                        self.post_data = chars.collect();
+                        true
+                    }
+                }
+            }
+            Key::Home | Key::PageUp => {
+                self.post_data = self.value();
+                self.pre_data = String::new();
+                true
+            }
+            Key::End | Key::PageDown => {
+                self.pre_data = self.value();
+                self.post_data = String::new();
+                true
+            }
+            _ => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn editing() {
+        let mut editor = LineEditor::with_value("añb");
+        assert_eq!(editor.cursor_back(), 0);
+
+        assert!(editor.handle(&Key::Left));
+        assert!(editor.handle(&Key::Left));
+        assert_eq!((editor.pre_data.as_str(), editor.cursor_back()), ("a", 2));
+        assert!(editor.handle(&Key::Char('x')));
+        assert!(editor.handle(&Key::Delete));
+        assert_eq!(editor.value(), "axb");
+
+        assert!(editor.handle(&Key::Home));
+        assert!(!editor.handle(&Key::Backspace));
+        assert!(!editor.handle(&Key::Left));
+        assert!(editor.handle(&Key::End));
+        assert!(!editor.handle(&Key::Right));
+        assert!(!editor.handle(&Key::Delete));
+        assert!(!editor.handle(&Key::Char('\n')));
+        assert!(editor.handle(&Key::Backspace));
+        assert_eq!(editor.value(), "ax");
+
+        editor.clear();
+        assert!(editor.is_empty());
+
+        let mut editor = LineEditor::with_value("日本語");
+        assert!(editor.handle(&Key::Left));
+        assert!(editor.handle(&Key::Left));
+        assert_eq!(editor.cursor_back(), 4);
+    }
+}
@@ -0,0 +1,127 @@
+use std::ops::Range;
+
+use crate::themes::FormatTheme;
+
+/// Cursor and scroll window shared by the list based prompts
+#[derive(Clone, Debug, PartialEq)]
+pub struct ListView {
+    pub cursor: usize,
+    pub offset: usize,
+    /// Rows shown at once, `0` shows every row
+    pub page_size: usize,
+}
+
+impl ListView {
+    pub fn new(page_size: usize) -> Self {
+        ListView {
+            cursor: 0,
+            offset: 0,
+            page_size,
+        }
+    }
+
+    fn page(&self, len: usize) -> usize {
+        if self.page_size == 0 {
+            len
+        } else {
+            self.page_size.min(len)
+        }
+    }
+
+    /// Keep the cursor inside the list and the window around the cursor
+    pub fn scroll(&mut self, len: usize) {
+        if len == 0 {
+            self.cursor = 0;
+            self.offset = 0;
+            return;
+        }
+
+        self.cursor = self.cursor.min(len - 1);
+        let page = self.page(len);
+        if self.cursor < self.offset {
+            self.offset = self.cursor;
+        } else if self.cursor >= self.offset + page {
+            self.offset = self.cursor + 1 - page;
+        }
+        self.offset = self.offset.min(len - page);
+    }
+
+    pub fn select(&mut self, idx: usize, len: usize) {
+        self.cursor = idx;
+        self.scroll(len);
+    }
+
+    pub fn up(&mut self, len: usize) {
+        self.select(self.cursor.saturating_sub(1), len);
+    }
+
+    pub fn down(&mut self, len: usize) {
+        self.select(self.cursor + 1, len);
+    }
+
+    pub fn page_up(&mut self, len: usize) {
+        self.select(self.cursor.saturating_sub(self.page(len).max(1)), len);
+    }
+
+    pub fn page_down(&mut self, len: usize) {
+        self.select(self.cursor + self.page(len).max(1), len);
+    }
+
+    pub fn home(&mut self, len: usize) {
+        self.select(0, len);
+    }
+
+    pub fn end(&mut self, len: usize) {
+        self.select(len.saturating_sub(1), len);
+    }
+
+    /// Indexes of the rows inside the window
+    pub fn visible(&self, len: usize) -> Range<usize> {
+        let start = self.offset.min(len);
+        start..(start + self.page(len)).min(len)
+    }
+
+    /// Rows inside the window with the cursor marker, plus a dim line when
+    /// there are hidden rows
+    pub fn render<T: FormatTheme>(&self, theme: &T, rows: &[String]) -> Vec<String> {
+        let range = self.visible(rows.len());
+        let hidden_above = range.start;
+        let hidden_below = rows.len() - range.end;
+
+        let mut lines: Vec<String> = range
+            .map(|i| theme.format_list_item(&rows[i], i == self.cursor))
+            .collect();
+
+        if hidden_above > 0 || hidden_below > 0 {
+            lines.push(theme.format_hint(&format!(
+                "  ({} more above, {} more below)",
+                hidden_above, hidden_below
+            )));
+        }
+
+        lines
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn scrolling() {
+        let mut view = ListView::new(3);
+        view.end(10);
+        assert_eq!(view.visible(10), 7..10);
+
+        view.up(10);
+        view.up(10);
+        assert_eq!(view.visible(10), 7..10);
+        view.up(10);
+        assert_eq!(view.visible(10), 6..9);
+
+        view.page_up(10);
+        assert_eq!(view.cursor, 3);
+        view.home(10);
+        assert_eq!(view.visible(10), 0..3);
+    }
+}
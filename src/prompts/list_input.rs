@@ -0,0 +1,335 @@
+use std::fmt::Display;
+use std::io::{Read, Write};
+
+use termion::event::{Event, Key};
+use termion::input::Events;
+
+use crate::themes::FormatTheme;
+use crate::{
+    ansi, escape_separators, io_handl, is_resize, split_unescaped, unescape, Frame, FrameWriter,
+    LineEditor, MyFromStr, PromptBase, PromptError, PromptType,
+};
+
+/// Check run on every parsed entry, returning the message to show
+pub type ItemValidator<V> = fn(&V) -> Result<(), String>;
+
+/// Collect several values, one per Enter. Backspace on an empty input
+/// removes the last entry and a blank Enter or Ctrl+D finishes.
+#[derive(Debug)]
+pub struct ListInputPrompt<'a, T: FormatTheme, V: MyFromStr> {
+    pub base: &'a mut PromptBase,
+    pub theme: &'a T,
+    /// Type every entry is checked against before it is parsed
+    pub item_type: PromptType,
+    pub validator: Option<ItemValidator<V>>,
+    pub min: usize,
+    pub max: Option<usize>,
+    /// Show entries as `[chips]` before the input instead of one per line
+    pub chips: bool,
+}
+
+impl<'a, T: FormatTheme, V: MyFromStr> ListInputPrompt<'a, T, V>
+where
+    V::Err_: Display,
+{
+    /// Same prompt returning entries parsed as `U`, the validator is dropped
+    pub fn parse_as<U: MyFromStr>(self) -> ListInputPrompt<'a, T, U> {
+        ListInputPrompt {
+            base: self.base,
+            theme: self.theme,
+            item_type: self.item_type,
+            validator: None,
+            min: self.min,
+            max: self.max,
+            chips: self.chips,
+        }
+    }
+
+    /// Parse and validate a single entry
+    pub fn parse_item(&self, raw: &str) -> Result<V, String> {
+        self.item_type.validate(raw)?;
+        let value = <V as MyFromStr>::from_str(raw).map_err(|err| err.to_string())?;
+        if let Some(validator) = self.validator {
+            validator(&value)?;
+        }
+
+        Ok(value)
+    }
+
+    fn check_count(&self, count: usize) -> Result<(), String> {
+        if count < self.min {
+            return Err(format!("Enter at least {} entries", self.min));
+        }
+        if let Some(max) = self.max.filter(|max| count > *max) {
+            return Err(format!("Enter at most {} entries", max));
+        }
+
+        Ok(())
+    }
+
+    /// Keep the typed entries in the session being recorded
+    fn record(&self, raw: &[String]) {
+        let escaped: Vec<String> = raw
+            .iter()
+            .map(|item| escape_separators(item, &[',']))
+            .collect();
+        self.base.record(&escaped.join(", "));
+    }
+
+    /// Entries separated by commas, checked the same way as typed ones. A
+    /// `\` escapes a comma in an entry.
+    fn parse_list(&self, line: &str) -> Result<Vec<V>, String> {
+        let items = match line.trim() {
+            "" => vec![],
+            line => split_unescaped(line, ',')
+                .into_iter()
+                .map(|item| self.parse_item(&unescape(item.trim(), &[','])))
+                .collect::<Result<Vec<V>, String>>()?,
+        };
+        self.check_count(items.len())?;
+
+        Ok(items)
+    }
+
+    pub fn prompt<R: Read, W: Write>(
+        &mut self,
+        stdin: &mut Events<R>,
+        stdout: &mut W,
+        go_back: Option<bool>,
+    ) -> Result<Vec<V>, PromptError> {
+        if let Some(answer) = self
+            .base
+            .resolve(self.theme, stdin, stdout, |line| self.parse_list(line))
+        {
+            return answer;
+        }
+
+        io_handl!(self.base.start_line(stdout, go_back.unwrap_or(true)));
+
+        let mut writer = FrameWriter::new();
+        let mut editor = LineEditor::with_value(self.base.initial.clone().unwrap_or_default());
+        let mut raw: Vec<String> = vec![];
+        let mut items: Vec<V> = vec![];
+        let mut err: Option<String> = None;
+
+        macro_rules! update {
+            () => {
+                let mut frame = Frame::new();
+                let mut line = self.theme.format_prompt(self.base);
+                if self.chips {
+                    for item in &raw {
+                        line.push_str(&format!(
+                            "{} ",
+                            self.theme.format_input(&format!("[{}]", item))
+                        ));
+                    }
+                }
+                if raw.is_empty() && editor.is_empty() {
+                    line.push_str(&self.theme.format_default(self.base));
+                }
+                line.push_str(&self.theme.format_input(&editor.value()));
+                let mut back = editor.cursor_back();
+                if editor.is_empty() {
+                    let placeholder = self.base.format_placeholder(self.theme);
+                    back += ansi::width(&placeholder);
+                    line.push_str(&placeholder);
+                }
+                frame.push(line);
+                frame.cursor_here(back);
+
+                if !self.chips {
+                    for item in &raw {
+                        frame.push(format!(
+                            "  {} {}",
+                            self.theme.format_hint(self.theme.glyphs().bullet),
+                            self.theme.format_input(item)
+                        ));
+                    }
+                }
+                if let Some(err) = &err {
+                    frame.push(self.theme.format_error(err));
+                }
+                self.base.add_help(self.theme, &mut frame);
+                io_handl!(writer.draw(stdout, &frame));
+            };
+        }
+
+        macro_rules! finish {
+            ($line:expr) => {
+                io_handl!(writer.finish_with(stdout, $line));
+            };
+        }
+
+        update!();
+
+        for c in stdin {
+            let evt = c.unwrap();
+            if is_resize(&evt) {
+                io_handl!(writer.redraw(stdout));
+                continue;
+            }
+            match evt {
+                Event::Key(Key::Ctrl('c') | Key::Esc) => {
+                    finish!(self.theme.format_cancelled(self.base));
+                    return Err(PromptError::KeyboardInterrupt);
+                }
+                Event::Key(Key::Char('\n') | Key::Ctrl('d')) if editor.is_empty() => {
+                    if raw.is_empty() {
+                        if let Some(default) = &self.base.default {
+                            match self.parse_list(default) {
+                                Ok(parsed) => {
+                                    self.base.record(default);
+                                    finish!(self.base.answered_line(self.theme, default));
+                                    return Ok(parsed);
+                                }
+                                Err(msg) => err = Some(msg),
+                            }
+                            update!();
+                            continue;
+                        }
+                    }
+
+                    match self.check_count(raw.len()) {
+                        Ok(()) => {
+                            self.record(&raw);
+                            finish!(self.base.answered_line(self.theme, &raw.join(", ")));
+                            return Ok(items);
+                        }
+                        Err(msg) => err = Some(msg),
+                    }
+                }
+                Event::Key(Key::Char('\n') | Key::Ctrl('d')) => {
+                    if let Some(max) = self.max.filter(|max| raw.len() >= *max) {
+                        err = Some(format!("Enter at most {} entries", max));
+                        update!();
+                        continue;
+                    }
+
+                    let value = editor.value();
+                    match self.parse_item(&value) {
+                        Ok(item) => {
+                            raw.push(value);
+                            items.push(item);
+                            editor.clear();
+                            err = None;
+                        }
+                        Err(msg) => {
+                            err = Some(msg);
+                            update!();
+                            continue;
+                        }
+                    }
+
+                    if let Event::Key(Key::Ctrl('d')) = evt {
+                        if let Err(msg) = self.check_count(raw.len()) {
+                            err = Some(msg);
+                        } else {
+                            self.record(&raw);
+                            finish!(self.base.answered_line(self.theme, &raw.join(", ")));
+                            return Ok(items);
+                        }
+                    }
+                }
+                Event::Key(Key::Backspace) if editor.is_empty() => {
+                    if raw.pop().is_none() {
+                        continue;
+                    }
+                    items.pop();
+                    err = None;
+                }
+                Event::Key(key) => {
+                    if !editor.handle(&key) {
+                        continue;
+                    }
+                    err = None;
+                }
+                _ => continue,
+            };
+
+            update!();
+        }
+
+        Ok(items)
+    }
+
+    pub fn prompt_handled<R: Read, W: Write>(
+        &mut self,
+        stdin: &mut Events<R>,
+        stdout: &mut W,
+        go_back: Option<bool>,
+    ) -> Result<Vec<V>, PromptError> {
+        match &self.base.prompt_type {
+            PromptType::List => {}
+            _ => {
+                return Err(PromptError::Custom(String::from(
+                    "Prompt struct only can be List",
+                )));
+            }
+        };
+
+        self.prompt(stdin, stdout, go_back)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{themes::DefaultTheme, TermRead};
+
+    #[test]
+    pub fn counts() {
+        let mut base = PromptBase::test(PromptType::List);
+        let prompt: ListInputPrompt<DefaultTheme, u8> = ListInputPrompt {
+            base: &mut base,
+            theme: &DefaultTheme,
+            item_type: PromptType::UInt,
+            validator: None,
+            min: 1,
+            max: Some(2),
+            chips: false,
+        };
+
+        assert_eq!(prompt.parse_list("1, 2"), Ok(vec![1, 2]));
+        assert!(prompt.parse_list("1, x").is_err());
+        assert_eq!(
+            prompt.parse_list(""),
+            Err(String::from("Enter at least 1 entries"))
+        );
+        assert_eq!(
+            prompt.parse_list("1, 2, 3"),
+            Err(String::from("Enter at most 2 entries"))
+        );
+    }
+
+    #[test]
+    pub fn default_is_counted() {
+        let mut out = Vec::new();
+
+        let mut base = PromptBase {
+            default: Some(String::from("a, b, c")),
+            ..PromptBase::test(PromptType::List)
+        };
+        let mut prompt: ListInputPrompt<DefaultTheme, String> = ListInputPrompt {
+            base: &mut base,
+            theme: &DefaultTheme,
+            item_type: PromptType::String,
+            validator: None,
+            min: 0,
+            max: Some(2),
+            chips: false,
+        };
+        let mut stdin = (&b"\r"[..]).events();
+        let items = prompt.prompt(&mut stdin, &mut out, Some(false)).unwrap();
+        assert!(items.is_empty());
+        assert!(String::from_utf8_lossy(&out).contains("Enter at most 2 entries"));
+
+        let mut base = PromptBase {
+            default: Some(String::from("a, b")),
+            ..PromptBase::test(PromptType::List)
+        };
+        prompt.base = &mut base;
+        let mut stdin = (&b"\r"[..]).events();
+        let items = prompt.prompt(&mut stdin, &mut out, Some(false)).unwrap();
+        assert_eq!(items, ["a", "b"]);
+    }
+}
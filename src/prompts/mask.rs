@@ -0,0 +1,487 @@
+use std::io::{Read, Write};
+
+use termion::event::{Event, Key};
+use termion::input::Events;
+
+use crate::themes::FormatTheme;
+use crate::{io_handl, is_resize, Frame, FrameWriter, PromptBase, PromptError, PromptType};
+
+/// Which characters an input slot of a mask accepts
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SlotClass {
+    Digit,
+    Letter,
+    Alphanumeric,
+    Any,
+}
+
+impl SlotClass {
+    pub fn accepts(&self, ch: char) -> bool {
+        match self {
+            SlotClass::Digit => ch.is_ascii_digit(),
+            SlotClass::Letter => ch.is_alphabetic(),
+            SlotClass::Alphanumeric => ch.is_alphanumeric(),
+            SlotClass::Any => !ch.is_control(),
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            SlotClass::Digit => "a digit",
+            SlotClass::Letter => "a letter",
+            SlotClass::Alphanumeric => "a letter or digit",
+            SlotClass::Any => "any character",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum MaskSlot {
+    Literal(char),
+    Input(SlotClass),
+}
+
+/// A fixed format parsed from a pattern.
+///
+/// `#` and `9` take a digit, `A` a letter, `*` a letter or digit and `?`
+/// any character. Everything else is a literal, use `\` to escape one of
+/// the slot characters.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Mask {
+    slots: Vec<MaskSlot>,
+}
+
+impl Mask {
+    pub fn parse(pattern: &str) -> Self {
+        let mut slots = vec![];
+        let mut chars = pattern.chars();
+
+        while let Some(ch) = chars.next() {
+            slots.push(match ch {
+                '#' | '9' => MaskSlot::Input(SlotClass::Digit),
+                'A' => MaskSlot::Input(SlotClass::Letter),
+                '*' => MaskSlot::Input(SlotClass::Alphanumeric),
+                '?' => MaskSlot::Input(SlotClass::Any),
+                '\\' => MaskSlot::Literal(chars.next().unwrap_or('\\')),
+                _ => MaskSlot::Literal(ch),
+            });
+        }
+
+        Mask { slots }
+    }
+
+    pub fn slots(&self) -> &[MaskSlot] {
+        &self.slots
+    }
+
+    pub fn len(&self) -> usize {
+        self.slots.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.slots.is_empty()
+    }
+}
+
+/// Mask with the characters typed so far and a cursor over its slots
+#[derive(Clone, Debug, PartialEq)]
+pub struct MaskedInput {
+    mask: Mask,
+    values: Vec<Option<char>>,
+    cursor: usize,
+}
+
+impl MaskedInput {
+    pub fn new(mask: Mask) -> Self {
+        let values = vec![None; mask.len()];
+        let mut input = MaskedInput {
+            mask,
+            values,
+            cursor: 0,
+        };
+        input.cursor = input.next_input(0);
+
+        input
+    }
+
+    pub fn cursor(&self) -> usize {
+        self.cursor
+    }
+
+    /// First input slot at or after `from`, or the end of the mask
+    fn next_input(&self, from: usize) -> usize {
+        (from..self.mask.len())
+            .find(|i| matches!(self.mask.slots[*i], MaskSlot::Input(_)))
+            .unwrap_or(self.mask.len())
+    }
+
+    /// Last input slot before `from`
+    fn prev_input(&self, from: usize) -> Option<usize> {
+        (0..from)
+            .rev()
+            .find(|i| matches!(self.mask.slots[*i], MaskSlot::Input(_)))
+    }
+
+    /// Put `ch` in the slot under the cursor. Typing the literal that was
+    /// just skipped over is accepted and does nothing.
+    pub fn insert(&mut self, ch: char) -> Result<(), SlotClass> {
+        let class = match self.mask.slots.get(self.cursor) {
+            Some(MaskSlot::Input(class)) => *class,
+            _ => return Ok(()),
+        };
+
+        if class.accepts(ch) {
+            self.values[self.cursor] = Some(ch);
+            self.cursor = self.next_input(self.cursor + 1);
+            return Ok(());
+        }
+
+        let skipped = self.mask.slots[..self.cursor]
+            .iter()
+            .rev()
+            .take_while(|slot| matches!(slot, MaskSlot::Literal(_)))
+            .any(|slot| *slot == MaskSlot::Literal(ch));
+        if skipped {
+            return Ok(());
+        }
+
+        Err(class)
+    }
+
+    pub fn backspace(&mut self) {
+        if let Some(i) = self.prev_input(self.cursor) {
+            self.values[i] = None;
+            self.cursor = i;
+        }
+    }
+
+    pub fn delete(&mut self) {
+        if self.cursor < self.values.len() {
+            self.values[self.cursor] = None;
+        }
+    }
+
+    pub fn left(&mut self) {
+        if let Some(i) = self.prev_input(self.cursor) {
+            self.cursor = i;
+        }
+    }
+
+    pub fn right(&mut self) {
+        if self.cursor < self.mask.len() {
+            self.cursor = self.next_input(self.cursor + 1);
+        }
+    }
+
+    pub fn home(&mut self) {
+        self.cursor = self.next_input(0);
+    }
+
+    pub fn end(&mut self) {
+        self.cursor = match self.values.iter().rposition(Option::is_some) {
+            Some(i) => self.next_input(i + 1),
+            None => self.next_input(0),
+        };
+    }
+
+    pub fn clear(&mut self) {
+        self.values = vec![None; self.mask.len()];
+        self.home();
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.iter().all(Option::is_none)
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.mask
+            .slots
+            .iter()
+            .zip(&self.values)
+            .all(|(slot, value)| matches!(slot, MaskSlot::Literal(_)) || value.is_some())
+    }
+
+    /// Value with the literals of the mask
+    pub fn value(&self) -> String {
+        self.display('_')
+    }
+
+    /// Only the typed characters
+    pub fn raw(&self) -> String {
+        self.values.iter().flatten().collect()
+    }
+
+    /// Value with `empty` in every unfilled slot
+    pub fn display(&self, empty: char) -> String {
+        self.mask
+            .slots
+            .iter()
+            .zip(&self.values)
+            .map(|(slot, value)| match (slot, value) {
+                (MaskSlot::Literal(lit), _) => *lit,
+                (MaskSlot::Input(_), Some(ch)) => *ch,
+                (MaskSlot::Input(_), None) => empty,
+            })
+            .collect()
+    }
+}
+
+#[derive(Debug)]
+pub struct MaskPrompt<'a, T: FormatTheme> {
+    pub base: &'a mut PromptBase,
+    pub theme: &'a T,
+    pub mask: Mask,
+    /// Return only the typed characters, without the literals
+    pub raw: bool,
+}
+
+impl<'a, T: FormatTheme> MaskPrompt<'a, T> {
+    fn format_input(&self, input: &MaskedInput) -> String {
+        let mut out = String::new();
+        for (i, ch) in input.display('_').chars().enumerate() {
+            let filled = match input.mask.slots[i] {
+                MaskSlot::Literal(_) => i < input.cursor(),
+                MaskSlot::Input(_) => input.values[i].is_some(),
+            };
+
+            if filled {
+                out.push_str(&self.theme.format_input(&ch.to_string()));
+            } else {
+                out.push_str(&self.theme.format_hint(&ch.to_string()));
+            }
+        }
+
+        out
+    }
+
+    /// The mask with `text` typed into it, failing at the first character
+    /// a slot does not take
+    fn fill(&self, text: &str) -> Result<MaskedInput, String> {
+        let mut input = MaskedInput::new(self.mask.clone());
+        for ch in text.chars() {
+            input
+                .insert(ch)
+                .map_err(|class| format!("'{}' is not valid, expected {}", ch, class.name()))?;
+        }
+
+        Ok(input)
+    }
+
+    pub fn prompt<R: Read, W: Write>(
+        &mut self,
+        stdin: &mut Events<R>,
+        stdout: &mut W,
+        go_back: Option<bool>,
+    ) -> Result<String, PromptError> {
+        if let Some(answer) = self.base.resolve(self.theme, stdin, stdout, |line| {
+            let input = self.fill(line)?;
+            if !input.is_complete() {
+                return Err(String::from("The value should fill the whole mask"));
+            }
+            Ok(if self.raw { input.raw() } else { input.value() })
+        }) {
+            return answer;
+        }
+
+        let mut input = match &self.base.initial {
+            Some(initial) => self.fill(initial).map_err(|msg| {
+                PromptError::Custom(format!("Invalid initial value {}: {}", initial, msg))
+            })?,
+            None => MaskedInput::new(self.mask.clone()),
+        };
+
+        io_handl!(self.base.start_line(stdout, go_back.unwrap_or(true)));
+
+        let mut writer = FrameWriter::new();
+
+        macro_rules! update {
+            ($err:expr) => {
+                let mut frame = Frame::new();
+                frame.push(format!(
+                    "{}{}{}",
+                    self.theme.format_prompt(self.base),
+                    self.theme.format_default(self.base),
+                    self.format_input(&input)
+                ));
+                frame.cursor_here(self.mask.len() - input.cursor());
+                if let Some(err) = $err {
+                    frame.push(self.theme.format_error(&err.to_string()));
+                }
+                self.base.add_help(self.theme, &mut frame);
+                io_handl!(writer.draw(stdout, &frame));
+            };
+            () => {
+                update!(Option::<String>::None)
+            };
+        }
+
+        update!();
+
+        for c in stdin {
+            let evt = c.unwrap();
+            if is_resize(&evt) {
+                io_handl!(writer.redraw(stdout));
+                continue;
+            }
+            match evt {
+                Event::Key(Key::Ctrl('c') | Key::Esc) => {
+                    io_handl!(writer.finish_with(stdout, self.theme.format_cancelled(self.base)));
+                    return Err(PromptError::KeyboardInterrupt);
+                }
+                Event::Key(Key::Char('\n')) => {
+                    // If has no value then try to use the default value
+                    if input.is_empty() {
+                        if let Some(default) = &self.base.default {
+                            match self.fill(default) {
+                                Ok(filled) => input = filled,
+                                Err(msg) => {
+                                    update!(Some(format!("Invalid default {}: {}", default, msg)));
+                                    continue;
+                                }
+                            }
+                        }
+                    }
+
+                    if !input.is_complete() {
+                        update!(Some("The value should fill the whole mask"));
+                        continue;
+                    }
+
+                    self.base.record(&input.value());
+                    io_handl!(writer
+                        .finish_with(stdout, self.base.answered_line(self.theme, &input.value())));
+
+                    return Ok(if self.raw { input.raw() } else { input.value() });
+                }
+                Event::Key(Key::Char(ch)) => match input.insert(ch) {
+                    Ok(()) => {
+                        update!();
+                    }
+                    Err(class) => {
+                        update!(Some(format!(
+                            "'{}' is not valid, expected {}",
+                            ch,
+                            class.name()
+                        )));
+                    }
+                },
+                Event::Key(Key::Left) => {
+                    input.left();
+                    update!();
+                }
+                Event::Key(Key::Right) => {
+                    input.right();
+                    update!();
+                }
+                Event::Key(Key::Backspace) => {
+                    input.backspace();
+                    update!();
+                }
+                Event::Key(Key::Delete) => {
+                    input.delete();
+                    update!();
+                }
+                Event::Key(Key::Home | Key::PageUp) => {
+                    input.home();
+                    update!();
+                }
+                Event::Key(Key::End | Key::PageDown) => {
+                    input.end();
+                    update!();
+                }
+                _ => {}
+            };
+        }
+
+        Ok(if self.raw { input.raw() } else { input.value() })
+    }
+
+    pub fn prompt_handled<R: Read, W: Write>(
+        &mut self,
+        stdin: &mut Events<R>,
+        stdout: &mut W,
+        go_back: Option<bool>,
+    ) -> Result<String, PromptError> {
+        match &self.base.prompt_type {
+            PromptType::Mask => {}
+            _ => {
+                return Err(PromptError::Custom(String::from(
+                    "Prompt struct only can be Mask",
+                )));
+            }
+        };
+
+        if self.mask.is_empty() {
+            return Err(PromptError::Custom(String::from(
+                "The mask pattern is empty",
+            )));
+        }
+
+        self.prompt(stdin, stdout, go_back)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{themes::DefaultTheme, TermRead};
+
+    #[test]
+    pub fn literals() {
+        let mut input = MaskedInput::new(Mask::parse("(###) ###-####"));
+        for ch in "5551234567".chars() {
+            input.insert(ch).unwrap();
+        }
+        assert!(input.is_complete());
+        assert_eq!(input.value(), "(555) 123-4567");
+        assert_eq!(input.raw(), "5551234567");
+
+        let mut input = MaskedInput::new(Mask::parse("####-##-##"));
+        for ch in "2024-05".chars() {
+            input.insert(ch).unwrap();
+        }
+        assert_eq!(input.value(), "2024-05-__");
+        input.backspace();
+        input.backspace();
+        assert_eq!(input.value(), "2024-__-__");
+    }
+
+    #[test]
+    pub fn classes() {
+        let mut input = MaskedInput::new(Mask::parse("AA-9999"));
+        assert_eq!(input.insert('1'), Err(SlotClass::Letter));
+        input.insert('A').unwrap();
+        input.insert('B').unwrap();
+        assert_eq!(input.insert('C'), Err(SlotClass::Digit));
+        assert_eq!(input.value(), "AB-____");
+        assert!(!input.is_complete());
+    }
+
+    #[test]
+    pub fn default_must_fit() {
+        let mut base = PromptBase {
+            default: Some(String::from("12-3x")),
+            ..PromptBase::test(PromptType::Mask)
+        };
+        let mut prompt = MaskPrompt {
+            base: &mut base,
+            theme: &DefaultTheme,
+            mask: Mask::parse("##-##"),
+            raw: true,
+        };
+        assert_eq!(prompt.fill("12-34").unwrap().raw(), "1234");
+        assert_eq!(
+            prompt.fill("12-3x").err(),
+            Some(String::from("'x' is not valid, expected a digit"))
+        );
+
+        // The default is not cut down to the part that fits
+        let mut stdin = (&b"\r1234\r"[..]).events();
+        let mut out = Vec::new();
+        assert_eq!(
+            prompt.prompt(&mut stdin, &mut out, Some(false)).unwrap(),
+            "1234"
+        );
+        assert!(String::from_utf8_lossy(&out).contains("Invalid default"));
+    }
+}
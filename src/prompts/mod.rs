@@ -1,9 +1,37 @@
+pub mod color_picker;
 mod common;
+pub mod date;
+mod frame;
 mod helper;
+pub mod key_value;
+mod line_editor;
+mod list;
+pub mod list_input;
+pub mod mask;
+pub mod multiline;
 pub mod number;
+mod plain;
+pub mod slider;
+pub mod sort;
+pub mod table;
 pub mod text;
+pub mod tree;
 
+pub use color_picker::*;
 pub use common::*;
+pub use date::*;
+pub use frame::*;
 pub use helper::*;
+pub use key_value::*;
+pub use line_editor::*;
+pub use list::*;
+pub use list_input::*;
+pub use mask::*;
+pub use multiline::*;
 pub use number::*;
+pub use plain::*;
+pub use slider::*;
+pub use sort::*;
+pub use table::*;
 pub use text::*;
+pub use tree::*;
@@ -0,0 +1,527 @@
+use std::collections::hash_map::RandomState;
+use std::env;
+use std::fs::{self, OpenOptions};
+use std::hash::{BuildHasher, Hasher};
+use std::io::{self, ErrorKind, Read, Write};
+use std::path::PathBuf;
+use std::process::{self, Command};
+
+use termion::event::{Event, Key};
+use termion::input::Events;
+
+use crate::themes::FormatTheme;
+use crate::{
+    ansi, io_handl, is_resize, plain_mode, read_line, FinishMode, Frame, FrameWriter, PromptBase,
+    PromptError, PromptType,
+};
+
+#[derive(Debug)]
+pub struct MultilinePrompt<'a, T: FormatTheme> {
+    pub base: &'a mut PromptBase,
+    pub theme: &'a T,
+    /// Show a line number gutter before each line
+    pub line_numbers: bool,
+    /// Open `$EDITOR` on a temporary file instead of editing inline
+    pub editor: bool,
+}
+
+/// Byte index of the `col` character of `line`
+fn byte_index(line: &str, col: usize) -> usize {
+    match line.char_indices().nth(col) {
+        Some((i, _)) => i,
+        None => line.len(),
+    }
+}
+
+fn char_len(line: &str) -> usize {
+    line.chars().count()
+}
+
+/// File for `$EDITOR`, removed once dropped
+struct TempFile {
+    path: PathBuf,
+}
+
+impl TempFile {
+    /// Create a new file with a random name and write `data` to it. The
+    /// file must not exist yet, so a link put in its place is never
+    /// followed.
+    fn create(data: &str) -> io::Result<Self> {
+        let mut options = OpenOptions::new();
+        options.write(true).create_new(true);
+        #[cfg(unix)]
+        std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+
+        loop {
+            let random = RandomState::new().build_hasher().finish();
+            let path = env::temp_dir().join(format!(
+                "rusky-request-{}-{:016x}.txt",
+                process::id(),
+                random
+            ));
+
+            match options.open(&path) {
+                Ok(mut file) => {
+                    let temp = TempFile { path };
+                    file.write_all(data.as_bytes())?;
+                    return Ok(temp);
+                }
+                Err(err) if err.kind() == ErrorKind::AlreadyExists => continue,
+                Err(err) => return Err(err),
+            }
+        }
+    }
+}
+
+impl Drop for TempFile {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
+impl<'a, T: FormatTheme> MultilinePrompt<'a, T> {
+    fn header(&self, hint: &str) -> String {
+        format!(
+            "{}{}{}",
+            self.theme.format_prompt(self.base),
+            self.theme.format_default(self.base),
+            self.theme.format_hint(hint)
+        )
+    }
+
+    /// First line of `data` followed by how many more there are
+    fn answered_line(&self, data: &str) -> Option<String> {
+        let count = data.lines().count();
+        let first = data.lines().next().unwrap_or("");
+        let mut answer = self.base.answered_line(self.theme, first)?;
+        if count > 1 {
+            let more = format!(" (+{} lines)", count - 1);
+            answer.push_str(&self.theme.format_hint(&more));
+        }
+
+        Some(answer)
+    }
+
+    fn gutter(&self, idx: usize, total: usize) -> String {
+        let column = self.theme.glyphs().column;
+        if self.line_numbers {
+            let width = total.to_string().len();
+            self.theme
+                .format_hint(&format!("{:>width$} {} ", idx + 1, column, width = width))
+        } else {
+            self.theme.format_hint(&format!("{} ", column))
+        }
+    }
+
+    fn frame(&self, lines: &[String], row: usize, col: usize, err: Option<&str>) -> Frame {
+        let mut frame = Frame::new();
+        frame.push(self.header("(ctrl+d to submit)"));
+
+        for (i, line) in lines.iter().enumerate() {
+            frame.push(format!(
+                "{}{}",
+                self.gutter(i, lines.len()),
+                self.theme.format_input(line)
+            ));
+            if i == row {
+                frame.cursor_here(ansi::width(&line[byte_index(line, col)..]));
+            }
+        }
+        if lines.len() == 1 && lines[0].is_empty() {
+            let placeholder = self.base.format_placeholder(self.theme);
+            frame.cursor_back = ansi::width(&placeholder);
+            frame.lines[1].push_str(&placeholder);
+        }
+
+        if let Some(err) = err {
+            frame.push(self.theme.format_error(err));
+        }
+
+        frame
+    }
+
+    /// Lines up to an empty one or the end of stdin, used in `plain_mode`
+    fn read_plain<R: Read, W: Write>(
+        &self,
+        stdin: &mut Events<R>,
+        stdout: &mut W,
+    ) -> Result<String, PromptError> {
+        io_handl!(writeln!(
+            stdout,
+            "{}(end with an empty line)",
+            self.base.plain_question(self.theme)
+        ));
+
+        let mut lines = vec![];
+        while let Some(line) = io_handl!(read_line(stdin)) {
+            if line.is_empty() {
+                break;
+            }
+            lines.push(line);
+        }
+
+        if !lines.is_empty() {
+            return Ok(lines.join("\n"));
+        }
+        match &self.base.default {
+            Some(default) => Ok(default.clone()),
+            None => Err(PromptError::Unanswered(self.base.text.clone())),
+        }
+    }
+
+    pub fn prompt<R: Read, W: Write>(
+        &mut self,
+        stdin: &mut Events<R>,
+        stdout: &mut W,
+        go_back: Option<bool>,
+    ) -> Result<String, PromptError> {
+        let prompt_type = &self.base.prompt_type;
+        if let Some(answer) = self.base.preset(self.theme, stdout, |text| {
+            prompt_type.validate(text).map(|_| text.to_string())
+        }) {
+            return answer;
+        }
+        if plain_mode() {
+            return self.read_plain(stdin, stdout);
+        }
+
+        io_handl!(self.base.start_line(stdout, go_back.unwrap_or(true)));
+
+        if self.editor {
+            return self.prompt_editor(stdin, stdout);
+        }
+
+        let mut writer = FrameWriter::new();
+        let mut lines: Vec<String> = match &self.base.initial {
+            Some(initial) => initial.split('\n').map(String::from).collect(),
+            None => vec![String::new()],
+        };
+        let mut row: usize = lines.len() - 1;
+        let mut col: usize = char_len(&lines[row]);
+
+        macro_rules! update {
+            ($err:expr) => {
+                let mut frame = self.frame(&lines, row, col, $err);
+                self.base.add_help(self.theme, &mut frame);
+                io_handl!(writer.draw(stdout, &frame));
+            };
+            () => {
+                update!(None)
+            };
+        }
+
+        update!();
+
+        for c in stdin {
+            let evt = c.unwrap();
+            if is_resize(&evt) {
+                io_handl!(writer.redraw(stdout));
+                continue;
+            }
+            match evt {
+                Event::Key(Key::Ctrl('c') | Key::Esc) => {
+                    io_handl!(writer.finish_with(stdout, self.theme.format_cancelled(self.base)));
+                    return Err(PromptError::KeyboardInterrupt);
+                }
+                Event::Key(Key::Ctrl('d') | Key::Alt('\r') | Key::Alt('\n')) => {
+                    let mut data = lines.join("\n");
+
+                    if data.is_empty() {
+                        match &self.base.default {
+                            Some(default) => {
+                                lines = default.split('\n').map(String::from).collect();
+                                data = default.clone();
+                            }
+                            None => {
+                                update!(Some("The text should contain 1 character or more"));
+                                continue;
+                            }
+                        }
+                    }
+
+                    self.base.record(&data);
+                    if self.base.finish == FinishMode::Keep {
+                        let mut frame = self.frame(&lines, 0, 0, None);
+                        frame.lines[0] = self.header("");
+                        io_handl!(writer.draw(stdout, &frame));
+                        io_handl!(writer.finish(stdout));
+                    } else {
+                        io_handl!(writer.finish_with(stdout, self.answered_line(&data)));
+                    }
+                    return Ok(data);
+                }
+                Event::Key(Key::Char('\n')) => {
+                    let at = byte_index(&lines[row], col);
+                    let tail = lines[row].split_off(at);
+                    lines.insert(row + 1, tail);
+                    row += 1;
+                    col = 0;
+                    update!();
+                }
+                Event::Key(Key::Char(ch)) => {
+                    let at = byte_index(&lines[row], col);
+                    lines[row].insert(at, ch);
+                    col += 1;
+                    update!();
+                }
+                Event::Key(Key::Left) => {
+                    if col > 0 {
+                        col -= 1;
+                    } else if row > 0 {
+                        row -= 1;
+                        col = char_len(&lines[row]);
+                    }
+                    update!();
+                }
+                Event::Key(Key::Right) => {
+                    if col < char_len(&lines[row]) {
+                        col += 1;
+                    } else if row + 1 < lines.len() {
+                        row += 1;
+                        col = 0;
+                    }
+                    update!();
+                }
+                Event::Key(Key::Up) if row > 0 => {
+                    row -= 1;
+                    col = col.min(char_len(&lines[row]));
+                    update!();
+                }
+                Event::Key(Key::Down) if row + 1 < lines.len() => {
+                    row += 1;
+                    col = col.min(char_len(&lines[row]));
+                    update!();
+                }
+                Event::Key(Key::Backspace) => {
+                    if col > 0 {
+                        col -= 1;
+                        let at = byte_index(&lines[row], col);
+                        lines[row].remove(at);
+                    } else if row > 0 {
+                        let line = lines.remove(row);
+                        row -= 1;
+                        col = char_len(&lines[row]);
+                        lines[row].push_str(&line);
+                    }
+                    update!();
+                }
+                Event::Key(Key::Delete) => {
+                    if col < char_len(&lines[row]) {
+                        let at = byte_index(&lines[row], col);
+                        lines[row].remove(at);
+                    } else if row + 1 < lines.len() {
+                        let line = lines.remove(row + 1);
+                        lines[row].push_str(&line);
+                    }
+                    update!();
+                }
+                Event::Key(Key::Home) => {
+                    col = 0;
+                    update!();
+                }
+                Event::Key(Key::End) => {
+                    col = char_len(&lines[row]);
+                    update!();
+                }
+                Event::Key(Key::PageUp) => {
+                    row = 0;
+                    col = 0;
+                    update!();
+                }
+                Event::Key(Key::PageDown) => {
+                    row = lines.len() - 1;
+                    col = char_len(&lines[row]);
+                    update!();
+                }
+                _ => {}
+            };
+        }
+
+        Ok(lines.join("\n"))
+    }
+
+    fn prompt_editor<R: Read, W: Write>(
+        &mut self,
+        stdin: &mut Events<R>,
+        stdout: &mut W,
+    ) -> Result<String, PromptError> {
+        let mut writer = FrameWriter::new();
+
+        macro_rules! update {
+            ($($line:expr),*) => {
+                let mut frame = Frame::new();
+                $(frame.push($line);)*
+                self.base.add_help(self.theme, &mut frame);
+                io_handl!(writer.draw(stdout, &frame));
+            };
+        }
+
+        update!(self.header("(press enter to open your editor)"));
+
+        for c in stdin {
+            let evt = c.unwrap();
+            if is_resize(&evt) {
+                io_handl!(writer.redraw(stdout));
+                continue;
+            }
+            match evt {
+                Event::Key(Key::Ctrl('c') | Key::Esc) => {
+                    io_handl!(writer.finish_with(stdout, self.theme.format_cancelled(self.base)));
+                    return Err(PromptError::KeyboardInterrupt);
+                }
+                Event::Key(Key::Char('\n')) => {
+                    let mut data = io_handl!(self.run_editor());
+                    if data.ends_with('\n') {
+                        data.pop();
+                    }
+
+                    if data.is_empty() {
+                        match &self.base.default {
+                            Some(default) => data = default.clone(),
+                            None => {
+                                update!(
+                                    self.header("(press enter to open your editor)"),
+                                    self.theme.format_error(
+                                        "The text should contain 1 character or more"
+                                    )
+                                );
+                                continue;
+                            }
+                        }
+                    }
+
+                    self.base.record(&data);
+                    io_handl!(writer.finish_with(stdout, self.answered_line(&data)));
+                    return Ok(data);
+                }
+                _ => {}
+            }
+        }
+
+        // Out of input, the text is left as it started like the inline mode
+        Ok(self.base.initial.clone().unwrap_or_default())
+    }
+
+    /// Open `$EDITOR` on a temporary file filled with the initial value, or
+    /// the default one, and read it back once the editor exits
+    fn run_editor(&self) -> io::Result<String> {
+        let seed = self.base.initial.as_ref().or(self.base.default.as_ref());
+        let file = TempFile::create(seed.map_or("", |text| text.as_str()))?;
+
+        let editor = env::var("EDITOR").unwrap_or_else(|_| String::from("vi"));
+        let mut parts = editor.split_whitespace();
+        let program = parts.next().unwrap_or("vi");
+
+        let status = Command::new(program).args(parts).arg(&file.path).status()?;
+        if !status.success() {
+            return Err(io::Error::other(format!(
+                "The editor exited with {}",
+                status
+            )));
+        }
+
+        fs::read_to_string(&file.path)
+    }
+
+    pub fn prompt_handled<R: Read, W: Write>(
+        &mut self,
+        stdin: &mut Events<R>,
+        stdout: &mut W,
+        go_back: Option<bool>,
+    ) -> Result<String, PromptError> {
+        match &self.base.prompt_type {
+            PromptType::MultiLine => {}
+            _ => {
+                return Err(PromptError::Custom(String::from(
+                    "Prompt struct only can be MultiLine",
+                )));
+            }
+        };
+
+        self.prompt(stdin, stdout, go_back)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{themes::DefaultTheme, TermRead};
+
+    #[test]
+    pub fn inline_editing() {
+        let mut base = PromptBase::test(PromptType::MultiLine);
+        let mut prompt = MultilinePrompt {
+            base: &mut base,
+            theme: &DefaultTheme,
+            line_numbers: true,
+            editor: false,
+        };
+        // The backspace at the start of "cd" joins it to the line above
+        let mut stdin = (&b"ab\rxy\x7f\x7fcd\x1b[H\x7f\r\x04"[..]).events();
+        let mut out = Vec::new();
+
+        let answer = prompt.prompt(&mut stdin, &mut out, Some(false));
+        assert_eq!(answer.unwrap(), "ab\ncd");
+    }
+
+    #[test]
+    pub fn empty_takes_default() {
+        let mut out = Vec::new();
+
+        let mut base = PromptBase::test(PromptType::MultiLine);
+        let mut prompt = MultilinePrompt {
+            base: &mut base,
+            theme: &DefaultTheme,
+            line_numbers: false,
+            editor: false,
+        };
+        let mut stdin = (&b"\x04"[..]).events();
+        prompt.prompt(&mut stdin, &mut out, Some(false)).unwrap();
+        assert!(String::from_utf8_lossy(&out).contains("1 character or more"));
+
+        let mut base = PromptBase {
+            default: Some(String::from("one\ntwo")),
+            ..PromptBase::test(PromptType::MultiLine)
+        };
+        let mut prompt = MultilinePrompt {
+            base: &mut base,
+            theme: &DefaultTheme,
+            line_numbers: false,
+            editor: false,
+        };
+        let mut stdin = (&b"\x04"[..]).events();
+        let answer = prompt.prompt(&mut stdin, &mut out, Some(false));
+        assert_eq!(answer.unwrap(), "one\ntwo");
+    }
+
+    #[test]
+    pub fn out_of_input() {
+        for editor in [false, true] {
+            let mut base = PromptBase {
+                initial: Some(String::from("draft")),
+                ..PromptBase::test(PromptType::MultiLine)
+            };
+            let mut prompt = MultilinePrompt {
+                base: &mut base,
+                theme: &DefaultTheme,
+                line_numbers: false,
+                editor,
+            };
+            let mut stdin = (&b""[..]).events();
+            let answer = prompt.prompt(&mut stdin, &mut Vec::new(), Some(false));
+            assert_eq!(answer.unwrap(), "draft");
+        }
+    }
+
+    #[test]
+    pub fn temp_file() {
+        let first = TempFile::create("text").unwrap();
+        let second = TempFile::create("").unwrap();
+        assert_ne!(first.path, second.path);
+        assert_eq!(fs::read_to_string(&first.path).unwrap(), "text");
+
+        let path = first.path.clone();
+        drop(first);
+        assert!(!path.exists());
+    }
+}
@@ -1,13 +1,11 @@
 use std::io::{self, Read, Write};
 
-use termion::clear::CurrentLine;
-use termion::cursor::{DetectCursorPos, Goto, Left, Up};
 use termion::event::{Event, Key};
 use termion::input::Events;
 
 use crate::themes::FormatTheme;
 use crate::PromptBase;
-use crate::{io_handl, Color, PromptError, PromptType};
+use crate::{ansi, io_handl, is_resize, Frame, FrameWriter, PromptError, PromptType, SlotClass};
 
 #[derive(Debug)]
 pub struct NumberPrompt<'a, T: FormatTheme> {
@@ -30,30 +28,12 @@ impl<'a, T: FormatTheme> NumberPrompt<'a, T> {
         stdout: &mut W,
         go_back: Option<bool>,
     ) -> Result<String, PromptError> {
-        let go_back = match go_back {
-            Some(v) => v,
-            None => true,
-        };
-
-        let mouse_pos = io_handl!(DetectCursorPos::cursor_pos(stdout));
-
-        let end_line = match &self.base.line {
-            None => {
-                self.base.line = Option::Some(mouse_pos.1);
-                mouse_pos.1 + 1
-            }
-            Some(line) => {
-                io_handl!(write!(stdout, "{}", Goto(0, *line)));
-                if go_back {
-                    mouse_pos.1 + 1
-                } else {
-                    *line + 1
-                }
-            }
-        };
-
-        io_handl!(self.write_text(stdout));
-        io_handl!(self.write_default(stdout));
+        let prompt_type = &self.base.prompt_type;
+        if let Some(answer) = self.base.resolve(self.theme, stdin, stdout, |line| {
+            prompt_type.validate(line).map(|_| line.to_string())
+        }) {
+            return answer;
+        }
 
         // true  = +
         // false = -
@@ -74,6 +54,34 @@ impl<'a, T: FormatTheme> NumberPrompt<'a, T> {
             _ => unreachable!(),
         };
 
+        if let Some(initial) = &self.base.initial {
+            let invalid = |msg: &str| {
+                PromptError::Custom(format!("Invalid initial value {}: {}", initial, msg))
+            };
+            self.base
+                .prompt_type
+                .validate(initial.trim())
+                .map_err(|msg| invalid(&msg))?;
+
+            for ch in initial.trim().chars() {
+                if ch == '-' && is_signed && pre_data.is_empty() {
+                    sign = false;
+                } else if ch == '.' && is_float && !has_dot {
+                    has_dot = true;
+                    pre_data.push('.');
+                    dot_pos = pre_data.len();
+                } else if SlotClass::Digit.accepts(ch) {
+                    pre_data.push(ch);
+                } else {
+                    return Err(invalid("only digits, - and . can be typed"));
+                }
+            }
+        }
+
+        io_handl!(self.base.start_line(stdout, go_back.unwrap_or(true)));
+
+        let mut writer = FrameWriter::new();
+
         macro_rules! get_data {
             () => {
                 format!("{}{}{}", if sign { "" } else { "-" }, pre_data, post_data)
@@ -87,43 +95,51 @@ impl<'a, T: FormatTheme> NumberPrompt<'a, T> {
         }
 
         macro_rules! update {
-            () => {
-                io_handl!(write!(stdout, "\n{}{}{}", Left(99), CurrentLine, Up(1)));
-                io_handl!(self.write_text(stdout));
-                io_handl!(write!(stdout, "{}", Color::Cyan.a(get_data!())));
-                if post_data.len() >= 1 {
-                    io_handl!(write!(stdout, "{}", Left(post_data.len() as u16)));
+            ($err:expr) => {
+                let data = get_data!();
+                let mut line = self.theme.format_prompt(self.base);
+                let mut back = post_data.len();
+                if data.is_empty() {
+                    let placeholder = self.base.format_placeholder(self.theme);
+                    back += ansi::width(&placeholder);
+                    line.push_str(&self.theme.format_default(self.base));
+                    line.push_str(&placeholder);
+                } else {
+                    line.push_str(&self.theme.format_input(&data));
+                }
+
+                let mut frame = Frame::new();
+                frame.push(line);
+                frame.cursor_here(back);
+                if let Some(err) = $err {
+                    frame.push(self.theme.format_error(err));
                 }
-                io_handl!(stdout.flush());
+                self.base.add_help(self.theme, &mut frame);
+                io_handl!(writer.draw(stdout, &frame));
+            };
+            () => {
+                update!(Option::<&str>::None)
             };
         }
 
         macro_rules! send_err {
             ($msg:expr) => {
-                io_handl!(write!(stdout, "\n{}{}", Left(9999), CurrentLine));
-                io_handl!(write!(
-                    stdout,
-                    "{}{}{}",
-                    CurrentLine,
-                    Color::Red.a($msg),
-                    Up(1)
-                ));
-                io_handl!(self.write_text(stdout));
-                io_handl!(write!(stdout, "{}", Color::Cyan.a(get_data!())));
-                if post_data.len() >= 1 {
-                    io_handl!(write!(stdout, "{}", Left(post_data.len() as u16)));
-                }
-                io_handl!(stdout.flush());
+                update!(Some($msg));
             };
         }
 
+        update!();
+
         for c in stdin {
             let evt = c.unwrap();
+            if is_resize(&evt) {
+                io_handl!(writer.redraw(stdout));
+                continue;
+            }
+
             match evt {
                 Event::Key(Key::Ctrl('c') | Key::Esc) => {
-                    update!();
-                    io_handl!(write!(stdout, "{}\n", Color::Red.a("<cancelled>")));
-                    io_handl!(stdout.flush());
+                    io_handl!(writer.finish_with(stdout, self.theme.format_cancelled(self.base)));
                     return Err(PromptError::KeyboardInterrupt);
                 }
                 Event::Key(Key::Char('\n')) => {
@@ -150,18 +166,14 @@ impl<'a, T: FormatTheme> NumberPrompt<'a, T> {
                         continue;
                     }
 
-                    io_handl!(write!(
-                        stdout,
-                        "{}\n{}",
-                        Goto(9999, end_line - 1),
-                        CurrentLine
-                    ));
-                    io_handl!(stdout.flush());
+                    self.base.record(&get_data!());
+                    let line = self.base.answered_line(self.theme, &get_data!());
+                    io_handl!(writer.finish_with(stdout, line));
                     break;
                 }
                 Event::Key(Key::Char(ch)) => {
                     // Numbers will push without validator
-                    if ch.is_ascii_digit() {
+                    if SlotClass::Digit.accepts(ch) {
                         pre_data.push(ch);
                         update!();
                     // '-' (minus) key will toggle sign
@@ -0,0 +1,325 @@
+use std::io::{self, Read, Write};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use termion::event::{Event, Key};
+use termion::input::Events;
+
+use crate::themes::FormatTheme;
+use crate::{
+    ansi, io_handl, mask_password, with_answers, Answers, FrameWriter, PromptBase, PromptError,
+    PromptType, RedactInput,
+};
+
+static PLAIN: AtomicBool = AtomicBool::new(false);
+
+/// Whether stdin and stdout are both terminals
+pub fn is_interactive() -> bool {
+    termion::is_tty(&io::stdin()) && termion::is_tty(&io::stdout())
+}
+
+/// Whether prompts ask on plain lines instead of reading keys. Turned on by
+/// `create_stdout!` when there is no terminal.
+pub fn plain_mode() -> bool {
+    PLAIN.load(Ordering::Relaxed)
+}
+
+pub fn set_plain_mode(plain: bool) {
+    PLAIN.store(plain, Ordering::Relaxed);
+}
+
+/// Next line of `stdin` without its newline, `None` once it ended
+pub fn read_line<R: Read>(stdin: &mut Events<R>) -> io::Result<Option<String>> {
+    let mut line = String::new();
+
+    for evt in stdin {
+        match evt? {
+            Event::Key(Key::Char('\n')) => return Ok(Some(line)),
+            Event::Key(Key::Char(ch)) => line.push(ch),
+            _ => {}
+        }
+    }
+
+    Ok(Some(line).filter(|line| !line.is_empty()))
+}
+
+/// Echo of the terminal on stdin turned off until this is dropped, so a
+/// password is not shown while it is typed
+struct NoEcho(libc::termios);
+
+impl NoEcho {
+    fn new() -> Option<Self> {
+        unsafe {
+            let mut term: libc::termios = std::mem::zeroed();
+            if libc::tcgetattr(libc::STDIN_FILENO, &mut term) != 0 {
+                return None;
+            }
+
+            let saved = term;
+            term.c_lflag &= !libc::ECHO;
+            if libc::tcsetattr(libc::STDIN_FILENO, libc::TCSANOW, &term) != 0 {
+                return None;
+            }
+            Some(NoEcho(saved))
+        }
+    }
+}
+
+impl Drop for NoEcho {
+    fn drop(&mut self) {
+        unsafe {
+            libc::tcsetattr(libc::STDIN_FILENO, libc::TCSANOW, &self.0);
+        }
+    }
+}
+
+impl PromptBase {
+    /// The question as plain text, with the default when there is one
+    pub fn plain_question<T: FormatTheme>(&self, theme: &T) -> String {
+        ansi::strip(&format!(
+            "{}{}",
+            theme.format_prompt(self),
+            theme.format_default(self)
+        ))
+    }
+
+    /// Line based prompt used when there is no terminal. An empty line takes
+    /// the default, then `check` turns the line into the answer or gives the
+    /// error to write before reading the next one.
+    pub fn read_plain<R, W, T, V, F>(
+        &self,
+        theme: &T,
+        stdin: &mut Events<R>,
+        stdout: &mut W,
+        mut check: F,
+    ) -> Result<V, PromptError>
+    where
+        R: Read,
+        W: Write,
+        T: FormatTheme,
+        F: FnMut(&str) -> Result<V, String>,
+    {
+        loop {
+            io_handl!(write!(stdout, "{}", self.plain_question(theme)));
+            io_handl!(stdout.flush());
+
+            let password = self.prompt_type == PromptType::Password;
+            let no_echo = if password && termion::is_tty(&io::stdin()) {
+                NoEcho::new()
+            } else {
+                None
+            };
+            let redact = password.then(RedactInput::new);
+            let line = read_line(stdin);
+            drop(redact);
+            let echoed = termion::is_tty(&io::stdin()) && no_echo.is_none();
+            drop(no_echo);
+
+            let line = match (io_handl!(line), &self.default) {
+                (Some(line), _) if !line.is_empty() => line,
+                (_, Some(default)) => default.clone(),
+                (Some(line), None) => line,
+                (None, None) => {
+                    io_handl!(writeln!(stdout));
+                    return Err(PromptError::Unanswered(self.text.clone()));
+                }
+            };
+
+            // A terminal on stdin already echoed the line, unless it was a
+            // password read with echo off
+            if !echoed {
+                if password {
+                    io_handl!(write!(stdout, "{}", mask_password(&line)));
+                } else {
+                    io_handl!(write!(stdout, "{}", line));
+                }
+                io_handl!(writeln!(stdout));
+            }
+
+            match check(&line) {
+                Ok(value) => {
+                    self.record(&line);
+                    return Ok(value);
+                }
+                Err(err) => io_handl!(writeln!(
+                    stdout,
+                    "{}",
+                    ansi::strip(&theme.format_error(&err))
+                )),
+            }
+        }
+    }
+
+    /// Answer given ahead of time through `set_answers`: the one under
+    /// `key`, or the default when defaults are accepted. Strict mode fails
+    /// when there is neither, otherwise `None` is returned and the prompt is
+    /// asked.
+    pub fn preset<W, T, V, F>(
+        &self,
+        theme: &T,
+        stdout: &mut W,
+        check: F,
+    ) -> Option<Result<V, PromptError>>
+    where
+        W: Write,
+        T: FormatTheme,
+        F: FnMut(&str) -> Result<V, String>,
+    {
+        let answers = with_answers(Answers::clone);
+        self.preset_with(&answers, theme, stdout, check)
+    }
+
+    /// `preset` with the given `answers`. The error of strict mode lists
+    /// this prompt with the other `keys` of the run that have no answer.
+    pub fn preset_with<W, T, V, F>(
+        &self,
+        answers: &Answers,
+        theme: &T,
+        stdout: &mut W,
+        mut check: F,
+    ) -> Option<Result<V, PromptError>>
+    where
+        W: Write,
+        T: FormatTheme,
+        F: FnMut(&str) -> Result<V, String>,
+    {
+        if let Some(line) = self.key.as_deref().and_then(|key| answers.get(key)) {
+            return Some(match check(&line) {
+                Ok(value) => self.write_preset(theme, stdout, &line).map(|_| value),
+                Err(err) => Err(PromptError::Custom(format!(
+                    "Invalid answer for {}: {}",
+                    self.key.as_deref().unwrap_or_default(),
+                    err
+                ))),
+            });
+        }
+
+        // The same as pressing enter right away, which takes the default
+        if answers.accept_defaults {
+            let line = self.default.clone().unwrap_or_default();
+            if let Ok(value) = check(&line) {
+                return Some(self.write_preset(theme, stdout, &line).map(|_| value));
+            }
+        }
+
+        if answers.strict {
+            let key = self.key.clone().unwrap_or_else(|| self.text.clone());
+            let mut missing = answers.missing(answers.keys.iter().map(String::as_str));
+            if !missing.contains(&key) {
+                missing.insert(0, key);
+            }
+            return Some(Err(PromptError::MissingAnswers(missing)));
+        }
+
+        None
+    }
+
+    /// `preset` answer or, in `plain_mode`, one read from stdin. `None` when
+    /// the prompt has to be asked with keys.
+    pub fn resolve<R, W, T, V, F>(
+        &self,
+        theme: &T,
+        stdin: &mut Events<R>,
+        stdout: &mut W,
+        mut check: F,
+    ) -> Option<Result<V, PromptError>>
+    where
+        R: Read,
+        W: Write,
+        T: FormatTheme,
+        F: FnMut(&str) -> Result<V, String>,
+    {
+        if let Some(answer) = self.preset(theme, stdout, &mut check) {
+            return Some(answer);
+        }
+        if plain_mode() {
+            return Some(self.read_plain(theme, stdin, stdout, check));
+        }
+
+        None
+    }
+
+    /// Leave the summary of an answer that was not typed
+    fn write_preset<T: FormatTheme, W: Write>(
+        &self,
+        theme: &T,
+        stdout: &mut W,
+        answer: &str,
+    ) -> Result<(), PromptError> {
+        self.record(answer);
+        let line = match self.answered_line(theme, answer) {
+            Some(line) => line,
+            None => return Ok(()),
+        };
+
+        if plain_mode() {
+            io_handl!(writeln!(stdout, "{}", ansi::strip(&line)));
+        } else {
+            io_handl!(FrameWriter::new().finish_with(stdout, line));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{themes::DefaultTheme, FinishMode, TermRead};
+
+    #[test]
+    pub fn plain_lines() {
+        let base = PromptBase::test(PromptType::UInt);
+        let mut stdin = (&b"abc\n42\n"[..]).events();
+        let mut out = Vec::new();
+
+        let answer = base.read_plain(&DefaultTheme, &mut stdin, &mut out, |line| {
+            base.prompt_type.validate(line).map(|_| line.to_string())
+        });
+        assert_eq!(answer.unwrap(), "42");
+
+        let answer = base.read_plain(&DefaultTheme, &mut stdin, &mut out, |line| {
+            Ok::<_, String>(line.to_string())
+        });
+        assert!(matches!(answer, Err(PromptError::Unanswered(_))));
+    }
+
+    #[test]
+    pub fn preset() {
+        let base = |key: &str, default: Option<&str>| PromptBase {
+            key: Some(String::from(key)),
+            default: default.map(String::from),
+            finish: FinishMode::Clear,
+            ..PromptBase::test(PromptType::UInt)
+        };
+        let check = |line: &str| PromptType::UInt.validate(line).map(|_| line.to_string());
+        let mut answers = Answers::new();
+        answers.insert("db.port", "5432");
+        let mut out = Vec::new();
+
+        let answer = base("db.port", None).preset_with(&answers, &DefaultTheme, &mut out, check);
+        assert_eq!(answer.unwrap().unwrap(), "5432");
+        let answer = base("db.user", None).preset_with(&answers, &DefaultTheme, &mut out, check);
+        assert!(answer.is_none());
+
+        answers.accept_defaults = true;
+        let answer =
+            base("db.user", Some("80")).preset_with(&answers, &DefaultTheme, &mut out, check);
+        assert_eq!(answer.unwrap().unwrap(), "80");
+
+        // No default passes the check, so the prompt is still asked
+        let answer = base("db.user", None).preset_with(&answers, &DefaultTheme, &mut out, check);
+        assert!(answer.is_none());
+
+        answers.accept_defaults = false;
+        answers.strict = true;
+        answers.keys = vec![
+            String::from("db.host"),
+            String::from("db.port"),
+            String::from("db.user"),
+        ];
+        let answer = base("db.host", None).preset_with(&answers, &DefaultTheme, &mut out, check);
+        assert!(matches!(
+            answer,
+            Some(Err(PromptError::MissingAnswers(missing))) if missing == ["db.host", "db.user"]
+        ));
+    }
+}
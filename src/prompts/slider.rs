@@ -0,0 +1,486 @@
+use std::fmt::{Debug, Display};
+use std::io::{Read, Write};
+
+use termion::event::{Event, Key};
+use termion::input::Events;
+
+use crate::themes::FormatTheme;
+use crate::{
+    io_handl, is_resize, shift_arrow, FinishMode, Frame, FrameWriter, MyFromStr, PromptBase,
+    PromptError, PromptType,
+};
+
+/// Numbers a slider can move over
+pub trait SliderValue: Copy + PartialOrd + Default + Display + Debug + MyFromStr {
+    const IS_FLOAT: bool;
+
+    fn to_f64(self) -> f64;
+    fn from_f64(value: f64) -> Self;
+}
+
+macro_rules! slider_value {
+    ($is_float:expr; $($t:ty),+) => {
+        $(
+            impl SliderValue for $t {
+                const IS_FLOAT: bool = $is_float;
+
+                fn to_f64(self) -> f64 {
+                    self as f64
+                }
+
+                fn from_f64(value: f64) -> Self {
+                    value.round() as $t
+                }
+            }
+        )+
+    };
+}
+
+slider_value!(false; i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+
+impl SliderValue for f32 {
+    const IS_FLOAT: bool = true;
+
+    fn to_f64(self) -> f64 {
+        self as f64
+    }
+
+    fn from_f64(value: f64) -> Self {
+        value as f32
+    }
+}
+
+impl SliderValue for f64 {
+    const IS_FLOAT: bool = true;
+
+    fn to_f64(self) -> f64 {
+        self
+    }
+
+    fn from_f64(value: f64) -> Self {
+        value
+    }
+}
+
+/// Handles of a slider and the bounds they move within
+#[derive(Clone, Debug)]
+struct Slider {
+    min: f64,
+    max: f64,
+    step: f64,
+    fine: f64,
+    decimals: usize,
+    handles: Vec<f64>,
+    active: usize,
+}
+
+impl Slider {
+    fn new<N: SliderValue>(min: N, max: N, step: Option<N>) -> Self {
+        let (min, max) = (min.to_f64(), max.to_f64());
+        let (min, max) = if min <= max { (min, max) } else { (max, min) };
+
+        let step = match step {
+            Some(step) if step.to_f64() > 0.0 => step.to_f64(),
+            _ if N::IS_FLOAT => (max - min) / 20.0,
+            _ => ((max - min) / 20.0).round().max(1.0),
+        };
+        let fine = if N::IS_FLOAT {
+            step / 10.0
+        } else {
+            (step / 10.0).round().max(1.0)
+        };
+        let decimals = if N::IS_FLOAT && fine > 0.0 {
+            (-fine.log10().floor()).max(0.0) as usize
+        } else {
+            0
+        };
+
+        Slider {
+            min,
+            max,
+            step,
+            fine,
+            decimals,
+            handles: vec![],
+            active: 0,
+        }
+    }
+
+    /// Clamp and round to the precision of the fine step
+    fn snap(&self, value: f64) -> f64 {
+        let scale = 10f64.powi(self.decimals as i32);
+        let value = (value * scale).round() / scale;
+
+        value.clamp(self.min, self.max)
+    }
+
+    fn set(&mut self, value: f64) {
+        let low = if self.active > 0 {
+            self.handles[self.active - 1]
+        } else {
+            self.min
+        };
+        let high = match self.handles.get(self.active + 1) {
+            Some(high) => *high,
+            None => self.max,
+        };
+
+        self.handles[self.active] = self.snap(value).clamp(low, high);
+    }
+
+    fn step_by(&mut self, delta: f64) {
+        self.set(self.handles[self.active] + delta);
+    }
+
+    /// Cell of each handle on a bar `width` cells wide
+    fn cells(&self, width: usize) -> Vec<usize> {
+        let span = self.max - self.min;
+        let last = width.max(1) - 1;
+        self.handles
+            .iter()
+            .map(|value| {
+                if span <= 0.0 {
+                    0
+                } else {
+                    (((value - self.min) / span) * last as f64).round() as usize
+                }
+            })
+            .collect()
+    }
+
+    fn show(&self, value: f64) -> String {
+        format!("{:.*}", self.decimals, value)
+    }
+
+    /// Handle keys shared by the single and range sliders, returning
+    /// `false` when the key is not one of them
+    fn handle(&mut self, evt: &Event) -> bool {
+        match shift_arrow(evt) {
+            Some(Key::Left | Key::Down) => {
+                self.step_by(-self.fine);
+                return true;
+            }
+            Some(Key::Right | Key::Up) => {
+                self.step_by(self.fine);
+                return true;
+            }
+            _ => {}
+        }
+
+        match evt {
+            Event::Key(Key::Left | Key::Down) => self.step_by(-self.step),
+            Event::Key(Key::Right | Key::Up) => self.step_by(self.step),
+            Event::Key(Key::PageDown) => self.step_by(-self.step * 10.0),
+            Event::Key(Key::PageUp) => self.step_by(self.step * 10.0),
+            Event::Key(Key::Home) => self.set(self.min),
+            Event::Key(Key::End) => self.set(self.max),
+            Event::Key(Key::Char('\t')) => self.active = (self.active + 1) % self.handles.len(),
+            Event::Key(Key::BackTab) => {
+                self.active = (self.active + self.handles.len() - 1) % self.handles.len()
+            }
+            _ => return false,
+        }
+
+        true
+    }
+}
+
+/// Runs the key loop for `slider`, returning the handles on submit
+fn run_slider<T: FormatTheme, R: Read, W: Write>(
+    base: &PromptBase,
+    theme: &T,
+    width: usize,
+    slider: &mut Slider,
+    stdin: &mut Events<R>,
+    stdout: &mut W,
+) -> Result<Vec<f64>, PromptError> {
+    let mut writer = FrameWriter::new();
+    // The handles need at least one cell to be drawn on
+    let width = width.max(1);
+
+    macro_rules! update {
+        ($active:expr) => {
+            let values: Vec<String> = slider.handles.iter().map(|v| slider.show(*v)).collect();
+            let mut frame = Frame::new();
+            frame.push(format!(
+                "{}{} {}",
+                theme.format_prompt(base),
+                theme.format_slider(width, &slider.cells(width), $active),
+                theme.format_input(&values.join(" - "))
+            ));
+            base.add_help(theme, &mut frame);
+            io_handl!(writer.draw(stdout, &frame));
+        };
+    }
+
+    update!(slider.active);
+
+    for c in stdin {
+        let evt = c.unwrap();
+        if is_resize(&evt) {
+            io_handl!(writer.redraw(stdout));
+            continue;
+        }
+        match evt {
+            Event::Key(Key::Ctrl('c') | Key::Esc) => {
+                io_handl!(writer.finish_with(stdout, theme.format_cancelled(base)));
+                return Err(PromptError::KeyboardInterrupt);
+            }
+            Event::Key(Key::Char('\n')) => {
+                let values: Vec<String> = slider.handles.iter().map(|v| slider.show(*v)).collect();
+                base.record(&values.join(".."));
+                if base.finish == FinishMode::Keep {
+                    update!(usize::MAX);
+                    io_handl!(writer.finish(stdout));
+                } else {
+                    let line = base.answered_line(theme, &values.join(" - "));
+                    io_handl!(writer.finish_with(stdout, line));
+                }
+                return Ok(slider.handles.clone());
+            }
+            evt => {
+                if slider.handle(&evt) {
+                    update!(slider.active);
+                }
+            }
+        }
+    }
+
+    Ok(slider.handles.clone())
+}
+
+fn check_bounds<N: SliderValue>(min: N, max: N) -> Result<(), PromptError> {
+    if min.to_f64().is_finite() && max.to_f64().is_finite() && min != max {
+        return Ok(());
+    }
+
+    Err(PromptError::Custom(format!(
+        "The slider bounds are invalid ({:?} - {:?})",
+        min, max
+    )))
+}
+
+/// `value` read as a number between the bounds, given in any order
+fn parse_in_bounds<N: SliderValue>(value: &str, min: N, max: N) -> Result<N, String> {
+    let (low, high) = if min <= max { (min, max) } else { (max, min) };
+    let value = <N as MyFromStr>::from_str(value.trim())
+        .map_err(|_| String::from("The value should be a number"))?;
+    if value < low || value > high {
+        return Err(format!("The value should be between {} and {}", low, high));
+    }
+
+    Ok(value)
+}
+
+#[derive(Debug)]
+pub struct SliderPrompt<'a, T: FormatTheme, N: SliderValue> {
+    pub base: &'a mut PromptBase,
+    pub theme: &'a T,
+    pub min: N,
+    pub max: N,
+    /// Size of a step with Left/Right, a twentieth of the range by default.
+    /// Shift moves a tenth of it.
+    pub step: Option<N>,
+    /// Cells used by the bar
+    pub width: usize,
+}
+
+impl<'a, T: FormatTheme, N: SliderValue> SliderPrompt<'a, T, N> {
+    pub fn prompt<R: Read, W: Write>(
+        &mut self,
+        stdin: &mut Events<R>,
+        stdout: &mut W,
+        go_back: Option<bool>,
+    ) -> Result<N, PromptError> {
+        if let Some(answer) = self.base.resolve(self.theme, stdin, stdout, |line| {
+            parse_in_bounds(line, self.min, self.max)
+        }) {
+            return answer;
+        }
+
+        io_handl!(self.base.start_line(stdout, go_back.unwrap_or(true)));
+
+        let mut slider = Slider::new(self.min, self.max, self.step);
+        let start = match &self.base.default {
+            Some(default) => match <N as MyFromStr>::from_str(default) {
+                Ok(value) => value.to_f64(),
+                Err(_) => slider.min,
+            },
+            None => slider.min,
+        };
+        slider.handles = vec![slider.snap(start)];
+
+        let handles = run_slider(
+            self.base,
+            self.theme,
+            self.width,
+            &mut slider,
+            stdin,
+            stdout,
+        )?;
+
+        Ok(N::from_f64(handles[0]))
+    }
+
+    pub fn prompt_handled<R: Read, W: Write>(
+        &mut self,
+        stdin: &mut Events<R>,
+        stdout: &mut W,
+        go_back: Option<bool>,
+    ) -> Result<N, PromptError> {
+        match &self.base.prompt_type {
+            PromptType::Slider => {}
+            _ => {
+                return Err(PromptError::Custom(String::from(
+                    "Prompt struct only can be Slider",
+                )));
+            }
+        };
+        check_bounds(self.min, self.max)?;
+
+        self.prompt(stdin, stdout, go_back)
+    }
+}
+
+/// Slider with a low and a high handle, Tab switches between them
+#[derive(Debug)]
+pub struct RangePrompt<'a, T: FormatTheme, N: SliderValue> {
+    pub base: &'a mut PromptBase,
+    pub theme: &'a T,
+    pub min: N,
+    pub max: N,
+    /// Size of a step with Left/Right, a twentieth of the range by default.
+    /// Shift moves a tenth of it.
+    pub step: Option<N>,
+    /// Cells used by the bar
+    pub width: usize,
+}
+
+impl<'a, T: FormatTheme, N: SliderValue> RangePrompt<'a, T, N> {
+    pub fn prompt<R: Read, W: Write>(
+        &mut self,
+        stdin: &mut Events<R>,
+        stdout: &mut W,
+        go_back: Option<bool>,
+    ) -> Result<(N, N), PromptError> {
+        if let Some(answer) = self.base.resolve(self.theme, stdin, stdout, |line| {
+            let (low, high) = line
+                .split_once("..")
+                .ok_or_else(|| String::from("The value should be written as low..high"))?;
+            let low = parse_in_bounds(low, self.min, self.max)?;
+            let high = parse_in_bounds(high, self.min, self.max)?;
+            if low > high {
+                return Err(String::from("The low end should not be above the high end"));
+            }
+            Ok((low, high))
+        }) {
+            return answer;
+        }
+
+        io_handl!(self.base.start_line(stdout, go_back.unwrap_or(true)));
+
+        let mut slider = Slider::new(self.min, self.max, self.step);
+
+        // Default is written as `low..high`
+        let parse = |s: &str| <N as MyFromStr>::from_str(s.trim()).ok();
+        let (low, high) = match self
+            .base
+            .default
+            .as_deref()
+            .and_then(|d| d.split_once(".."))
+        {
+            Some((low, high)) => match (parse(low), parse(high)) {
+                (Some(low), Some(high)) => (low.to_f64(), high.to_f64()),
+                _ => (slider.min, slider.max),
+            },
+            None => (slider.min, slider.max),
+        };
+        slider.handles = vec![slider.snap(low.min(high)), slider.snap(low.max(high))];
+
+        let handles = run_slider(
+            self.base,
+            self.theme,
+            self.width,
+            &mut slider,
+            stdin,
+            stdout,
+        )?;
+
+        Ok((N::from_f64(handles[0]), N::from_f64(handles[1])))
+    }
+
+    pub fn prompt_handled<R: Read, W: Write>(
+        &mut self,
+        stdin: &mut Events<R>,
+        stdout: &mut W,
+        go_back: Option<bool>,
+    ) -> Result<(N, N), PromptError> {
+        match &self.base.prompt_type {
+            PromptType::Range => {}
+            _ => {
+                return Err(PromptError::Custom(String::from(
+                    "Prompt struct only can be Range",
+                )));
+            }
+        };
+        check_bounds(self.min, self.max)?;
+
+        self.prompt(stdin, stdout, go_back)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn stepping() {
+        let mut slider = Slider::new(0, 100, None);
+        slider.handles = vec![50.0];
+        assert_eq!((slider.step, slider.fine), (5.0, 1.0));
+
+        slider.handle(&Event::Key(Key::Right));
+        assert_eq!(slider.handles, [55.0]);
+        slider.handle(&Event::Unsupported(b"\x1b[1;2D".to_vec()));
+        assert_eq!(slider.handles, [54.0]);
+        slider.handle(&Event::Key(Key::PageUp));
+        assert_eq!(slider.handles, [100.0]);
+        slider.handle(&Event::Key(Key::Home));
+        assert_eq!(slider.handles, [0.0]);
+        assert!(!slider.handle(&Event::Key(Key::Char('x'))));
+
+        let slider = Slider::new(1.0, 0.0, Some(0.25));
+        assert_eq!((slider.min, slider.max, slider.fine), (0.0, 1.0, 0.025));
+        assert_eq!(slider.decimals, 2);
+        assert_eq!(slider.show(0.5), "0.50");
+    }
+
+    #[test]
+    pub fn clamping() {
+        let mut slider = Slider::new(0, 10, Some(4));
+        slider.handles = vec![2.0, 6.0];
+
+        slider.step_by(-4.0);
+        assert_eq!(slider.handles, [0.0, 6.0]);
+        // A handle can not pass the other one
+        slider.step_by(8.0);
+        assert_eq!(slider.handles, [6.0, 6.0]);
+
+        slider.handle(&Event::Key(Key::Char('\t')));
+        slider.handle(&Event::Key(Key::End));
+        slider.handle(&Event::Key(Key::BackTab));
+        slider.handle(&Event::Key(Key::Left));
+        assert_eq!(slider.handles, [2.0, 10.0]);
+    }
+
+    #[test]
+    pub fn cells() {
+        let mut slider = Slider::new(0, 10, None);
+        slider.handles = vec![0.0, 5.0, 10.0];
+
+        assert_eq!(slider.cells(11), [0, 5, 10]);
+        assert_eq!(slider.cells(5), [0, 2, 4]);
+        assert_eq!(slider.cells(1), [0, 0, 0]);
+        assert_eq!(slider.cells(0), [0, 0, 0]);
+    }
+}
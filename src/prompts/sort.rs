@@ -0,0 +1,246 @@
+use std::io::{Read, Write};
+
+use termion::event::{Event, Key};
+use termion::input::Events;
+
+use crate::themes::FormatTheme;
+use crate::{
+    escape_separators, io_handl, is_resize, split_unescaped, unescape, Frame, FrameWriter,
+    ListView, PromptBase, PromptError, PromptType,
+};
+
+/// Reorder `items`. Space picks up the item under the cursor, Up and Down
+/// move it and Space drops it again.
+#[derive(Debug)]
+pub struct SortPrompt<'a, T: FormatTheme> {
+    pub base: &'a mut PromptBase,
+    pub theme: &'a T,
+    pub items: Vec<String>,
+    /// Rows shown at once, `0` shows every item
+    pub page_size: usize,
+}
+
+impl<'a, T: FormatTheme> SortPrompt<'a, T> {
+    fn rows(&self, order: &[usize], held: Option<usize>) -> Vec<String> {
+        order
+            .iter()
+            .enumerate()
+            .map(|(pos, idx)| {
+                let item = &self.items[*idx];
+                let num = self.theme.format_hint(&format!("{}.", pos + 1));
+                if Some(pos) == held {
+                    format!("{} {}", num, self.theme.format_highlight(item))
+                } else {
+                    format!("{} {}", num, item)
+                }
+            })
+            .collect()
+    }
+
+    /// Order written as the items separated by commas, empty keeps it. A
+    /// `\` escapes a comma in an item.
+    fn parse_order(&self, line: &str) -> Result<Vec<usize>, String> {
+        if line.trim().is_empty() {
+            return Ok((0..self.items.len()).collect());
+        }
+
+        let mut order: Vec<usize> = vec![];
+        for name in split_unescaped(line, ',') {
+            let name = unescape(name.trim(), &[',']);
+            let idx = (0..self.items.len())
+                .find(|idx| self.items[*idx] == name && !order.contains(idx))
+                .ok_or_else(|| format!("{} is not one of the items", name))?;
+            order.push(idx);
+        }
+
+        if order.len() != self.items.len() {
+            return Err(String::from("Every item should be listed once"));
+        }
+        Ok(order)
+    }
+
+    /// Returns the new order as indexes into `items`
+    pub fn prompt<R: Read, W: Write>(
+        &mut self,
+        stdin: &mut Events<R>,
+        stdout: &mut W,
+        go_back: Option<bool>,
+    ) -> Result<Vec<usize>, PromptError> {
+        if let Some(answer) = self
+            .base
+            .resolve(self.theme, stdin, stdout, |line| self.parse_order(line))
+        {
+            return answer;
+        }
+
+        // The default, written like an answer, is the order to start from
+        let mut order = match &self.base.default {
+            Some(default) => self.parse_order(default).map_err(|msg| {
+                PromptError::Custom(format!("Invalid default {}: {}", default, msg))
+            })?,
+            None => (0..self.items.len()).collect(),
+        };
+
+        io_handl!(self.base.start_line(stdout, go_back.unwrap_or(true)));
+
+        let mut writer = FrameWriter::new();
+        let mut view = ListView::new(self.page_size);
+        let mut held = false;
+        let len = order.len();
+
+        macro_rules! update {
+            () => {
+                let hint = if held {
+                    format!("({} to move, space to drop)", self.theme.glyphs().up_down)
+                } else {
+                    String::from("(space to pick up, enter to confirm)")
+                };
+                let mut frame = Frame::new();
+                frame.push(format!(
+                    "{}{}",
+                    self.theme.format_prompt(self.base),
+                    self.theme.format_hint(&hint)
+                ));
+                let rows = self.rows(&order, if held { Some(view.cursor) } else { None });
+                for line in view.render(self.theme, &rows) {
+                    frame.push(line);
+                }
+                self.base.add_help(self.theme, &mut frame);
+                io_handl!(writer.draw(stdout, &frame));
+            };
+        }
+
+        update!();
+
+        for c in stdin {
+            let evt = c.unwrap();
+            if is_resize(&evt) {
+                io_handl!(writer.redraw(stdout));
+                continue;
+            }
+            match evt {
+                Event::Key(Key::Ctrl('c') | Key::Esc) => {
+                    io_handl!(writer.finish_with(stdout, self.theme.format_cancelled(self.base)));
+                    return Err(PromptError::KeyboardInterrupt);
+                }
+                Event::Key(Key::Char('\n')) => {
+                    let names: Vec<&str> = order.iter().map(|i| self.items[*i].as_str()).collect();
+                    let escaped: Vec<String> = names
+                        .iter()
+                        .map(|name| escape_separators(name, &[',']))
+                        .collect();
+                    self.base.record(&escaped.join(", "));
+                    io_handl!(writer.finish_with(
+                        stdout,
+                        self.base.answered_line(self.theme, &names.join(", "))
+                    ));
+                    return Ok(order);
+                }
+                Event::Key(Key::Char(' ')) => {
+                    held = !held && len > 0;
+                }
+                Event::Key(Key::Up) if held && view.cursor > 0 => {
+                    order.swap(view.cursor, view.cursor - 1);
+                    view.up(len);
+                }
+                Event::Key(Key::Down) if held && view.cursor + 1 < len => {
+                    order.swap(view.cursor, view.cursor + 1);
+                    view.down(len);
+                }
+                Event::Key(Key::Home) if held => {
+                    let item = order.remove(view.cursor);
+                    order.insert(0, item);
+                    view.home(len);
+                }
+                Event::Key(Key::End) if held => {
+                    let item = order.remove(view.cursor);
+                    order.push(item);
+                    view.end(len);
+                }
+                Event::Key(Key::PageUp | Key::PageDown) if held => {
+                    let from = view.cursor;
+                    if evt == Event::Key(Key::PageUp) {
+                        view.page_up(len);
+                    } else {
+                        view.page_down(len);
+                    }
+                    let item = order.remove(from);
+                    order.insert(view.cursor, item);
+                }
+                Event::Key(Key::Up) => view.up(len),
+                Event::Key(Key::Down) => view.down(len),
+                Event::Key(Key::PageUp) => view.page_up(len),
+                Event::Key(Key::PageDown) => view.page_down(len),
+                Event::Key(Key::Home) => view.home(len),
+                Event::Key(Key::End) => view.end(len),
+                _ => continue,
+            };
+
+            update!();
+        }
+
+        Ok(order)
+    }
+
+    pub fn prompt_handled<R: Read, W: Write>(
+        &mut self,
+        stdin: &mut Events<R>,
+        stdout: &mut W,
+        go_back: Option<bool>,
+    ) -> Result<Vec<usize>, PromptError> {
+        match &self.base.prompt_type {
+            PromptType::Sort => {}
+            _ => {
+                return Err(PromptError::Custom(String::from(
+                    "Prompt struct only can be Sort",
+                )));
+            }
+        };
+
+        self.prompt(stdin, stdout, go_back)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{themes::DefaultTheme, TermRead};
+
+    #[test]
+    pub fn reordering() {
+        let mut base = PromptBase::test(PromptType::Sort);
+        let mut prompt = SortPrompt {
+            base: &mut base,
+            theme: &DefaultTheme,
+            items: ["a", "b", "c", "d", "e"].map(String::from).to_vec(),
+            page_size: 2,
+        };
+        // Move a down one and then a page, d to the top and e up a page
+        let keys = " \x1b[B\x1b[6~ \x1b[A \x1b[H \x1b[F \x1b[5~ \r";
+        let mut stdin = keys.as_bytes().events();
+        let mut out = Vec::new();
+
+        let order = prompt.prompt(&mut stdin, &mut out, Some(false));
+        assert_eq!(order.unwrap(), [3, 1, 4, 2, 0]);
+    }
+
+    #[test]
+    pub fn default_order() {
+        let prompt = |default: &str| {
+            let mut base = PromptBase {
+                default: Some(String::from(default)),
+                ..PromptBase::test(PromptType::Sort)
+            };
+            SortPrompt {
+                base: &mut base,
+                theme: &DefaultTheme,
+                items: ["a", "b", "c"].map(String::from).to_vec(),
+                page_size: 0,
+            }
+            .prompt(&mut (&b"\r"[..]).events(), &mut Vec::new(), Some(false))
+        };
+
+        assert_eq!(prompt("c, a, b").unwrap(), [2, 0, 1]);
+        assert!(matches!(prompt("c, x"), Err(PromptError::Custom(_))));
+    }
+}
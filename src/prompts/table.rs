@@ -0,0 +1,364 @@
+use std::io::{Read, Write};
+
+use termion::event::{Event, Key};
+use termion::input::Events;
+
+use crate::themes::FormatTheme;
+use crate::{
+    ansi, escape_separators, io_handl, is_resize, split_unescaped, unescape, Frame, FrameWriter,
+    LineEditor, ListView, PromptBase, PromptError, PromptType,
+};
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct TableColumn {
+    pub title: String,
+    /// Type every cell of the column is validated against
+    pub prompt_type: PromptType,
+}
+
+impl TableColumn {
+    pub fn new<S: Into<String>>(title: S, prompt_type: PromptType) -> Self {
+        TableColumn {
+            title: title.into(),
+            prompt_type,
+        }
+    }
+}
+
+fn pad(text: &str, width: usize) -> String {
+    let len = ansi::width(text);
+    format!("{}{}", text, " ".repeat(width.saturating_sub(len)))
+}
+
+/// Grid of cells. Arrows move between cells, Enter edits one, Ctrl+N adds
+/// a row, Ctrl+X removes it and Ctrl+D submits.
+#[derive(Debug)]
+pub struct TablePrompt<'a, T: FormatTheme> {
+    pub base: &'a mut PromptBase,
+    pub theme: &'a T,
+    pub columns: Vec<TableColumn>,
+    /// Rows the table starts with
+    pub rows: Vec<Vec<String>>,
+    /// Rows shown at once, `0` shows every row
+    pub page_size: usize,
+}
+
+impl<'a, T: FormatTheme> TablePrompt<'a, T> {
+    fn widths(&self, rows: &[Vec<String>], editing: Option<(usize, &LineEditor)>) -> Vec<usize> {
+        self.columns
+            .iter()
+            .enumerate()
+            .map(|(c, column)| {
+                let cells = rows.iter().map(|row| ansi::width(&row[c]));
+                let edit = match editing {
+                    Some((col, editor)) if col == c => ansi::width(&editor.value()) + 1,
+                    _ => 0,
+                };
+
+                cells
+                    .chain([ansi::width(&column.title), edit, 3])
+                    .max()
+                    .unwrap_or(3)
+            })
+            .collect()
+    }
+
+    /// First cell that does not validate against its column type
+    fn first_invalid(&self, rows: &[Vec<String>]) -> Option<(usize, usize, String)> {
+        for (r, row) in rows.iter().enumerate() {
+            for (c, column) in self.columns.iter().enumerate() {
+                if let Err(msg) = column.prompt_type.validate(&row[c]) {
+                    return Some((r, c, msg));
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Rows separated by `;` with cells separated by `,`, empty keeps `rows`.
+    /// A `\` escapes either of them in a cell.
+    fn parse_rows(&self, line: &str) -> Result<Vec<Vec<String>>, String> {
+        let rows: Vec<Vec<String>> = if line.trim().is_empty() {
+            self.rows.clone()
+        } else {
+            split_unescaped(line, ';')
+                .into_iter()
+                .map(|row| {
+                    split_unescaped(row, ',')
+                        .into_iter()
+                        .map(|cell| unescape(cell.trim(), &[',', ';']))
+                        .collect()
+                })
+                .collect()
+        };
+
+        if let Some(r) = rows.iter().position(|row| row.len() != self.columns.len()) {
+            return Err(format!(
+                "Row {} should have {} cells",
+                r + 1,
+                self.columns.len()
+            ));
+        }
+        if let Some((r, c, msg)) = self.first_invalid(&rows) {
+            return Err(format!("Row {}, {}: {}", r + 1, self.columns[c].title, msg));
+        }
+
+        Ok(rows)
+    }
+
+    pub fn prompt<R: Read, W: Write>(
+        &mut self,
+        stdin: &mut Events<R>,
+        stdout: &mut W,
+        go_back: Option<bool>,
+    ) -> Result<Vec<Vec<String>>, PromptError> {
+        if let Some(answer) = self
+            .base
+            .resolve(self.theme, stdin, stdout, |line| self.parse_rows(line))
+        {
+            return answer;
+        }
+
+        io_handl!(self.base.start_line(stdout, go_back.unwrap_or(true)));
+
+        let cols = self.columns.len();
+        let mut rows: Vec<Vec<String>> = self
+            .rows
+            .iter()
+            .map(|row| {
+                let mut row = row.clone();
+                row.resize(cols, String::new());
+                row
+            })
+            .collect();
+
+        let mut writer = FrameWriter::new();
+        let mut view = ListView::new(self.page_size);
+        let mut col: usize = 0;
+        let mut editing: Option<LineEditor> = None;
+        let mut err: Option<String> = None;
+
+        macro_rules! update {
+            () => {
+                let widths = self.widths(&rows, editing.as_ref().map(|e| (col, e)));
+                let mut frame = Frame::new();
+                frame.push(format!(
+                    "{}{}",
+                    self.theme.format_prompt(self.base),
+                    self.theme.format_hint(
+                        "(enter to edit, ctrl+n/ctrl+x to add/remove rows, ctrl+d to submit)"
+                    )
+                ));
+
+                let titles: Vec<String> = self
+                    .columns
+                    .iter()
+                    .zip(&widths)
+                    .map(|(column, width)| pad(&column.title, *width))
+                    .collect();
+                let glyphs = self.theme.glyphs();
+                let column = format!(" {} ", glyphs.column);
+                let cross = format!("{}{}{}", glyphs.rule, glyphs.cross, glyphs.rule);
+                let rules: Vec<String> = widths.iter().map(|w| glyphs.rule.repeat(*w)).collect();
+                frame.push(format!(
+                    "  {}",
+                    self.theme.format_header(&titles.join(&column))
+                ));
+                frame.push(format!("  {}", self.theme.format_hint(&rules.join(&cross))));
+
+                let lines: Vec<String> = rows
+                    .iter()
+                    .enumerate()
+                    .map(|(r, row)| {
+                        let cells: Vec<String> = row
+                            .iter()
+                            .enumerate()
+                            .map(|(c, cell)| match &editing {
+                                Some(editor) if r == view.cursor && c == col => {
+                                    self.theme.format_input(&pad(&editor.value(), widths[c]))
+                                }
+                                _ if r == view.cursor && c == col => {
+                                    self.theme.format_highlight(&pad(cell, widths[c]))
+                                }
+                                _ => pad(cell, widths[c]),
+                            })
+                            .collect();
+                        cells.join(&self.theme.format_hint(&column))
+                    })
+                    .collect();
+
+                if lines.is_empty() {
+                    frame.push(self.theme.format_hint("  (no rows, ctrl+n to add one)"));
+                }
+                for line in view.render(self.theme, &lines) {
+                    frame.push(line);
+                }
+
+                if let Some(editor) = &editing {
+                    let total: usize = widths.iter().sum::<usize>() + 3 * (cols - 1);
+                    let before: usize = widths[..col].iter().sum::<usize>() + 3 * col;
+                    frame.cursor_row = 3 + view.cursor - view.offset;
+                    frame.cursor_back = total - before - ansi::width(&editor.pre_data);
+                }
+
+                if let Some(err) = &err {
+                    frame.push(self.theme.format_error(err));
+                }
+                self.base.add_help(self.theme, &mut frame);
+                io_handl!(writer.draw(stdout, &frame));
+            };
+        }
+
+        update!();
+
+        for c in stdin {
+            let evt = c.unwrap();
+            if is_resize(&evt) {
+                io_handl!(writer.redraw(stdout));
+                continue;
+            }
+            let len = rows.len();
+
+            if let Event::Key(Key::Ctrl('c')) = evt {
+                io_handl!(writer.finish_with(stdout, self.theme.format_cancelled(self.base)));
+                return Err(PromptError::KeyboardInterrupt);
+            }
+
+            if let Some(editor) = &mut editing {
+                match evt {
+                    Event::Key(Key::Esc) => {
+                        editing = None;
+                        err = None;
+                    }
+                    Event::Key(Key::Char('\n' | '\t')) => {
+                        let value = editor.value();
+                        match self.columns[col].prompt_type.validate(&value) {
+                            Ok(()) => {
+                                rows[view.cursor][col] = value;
+                                editing = None;
+                                err = None;
+                                if let Event::Key(Key::Char('\t')) = evt {
+                                    col = (col + 1) % cols;
+                                }
+                            }
+                            Err(msg) => err = Some(msg),
+                        }
+                    }
+                    Event::Key(key) => {
+                        if !editor.handle(&key) {
+                            continue;
+                        }
+                    }
+                    _ => continue,
+                }
+
+                update!();
+                continue;
+            }
+
+            match evt {
+                Event::Key(Key::Esc) => {
+                    io_handl!(writer.finish_with(stdout, self.theme.format_cancelled(self.base)));
+                    return Err(PromptError::KeyboardInterrupt);
+                }
+                Event::Key(Key::Ctrl('d') | Key::Alt('\r') | Key::Alt('\n')) => {
+                    if let Some((r, c, msg)) = self.first_invalid(&rows) {
+                        view.select(r, len);
+                        col = c;
+                        err = Some(format!("Row {}, {}: {}", r + 1, self.columns[c].title, msg));
+                        update!();
+                        continue;
+                    }
+
+                    let cells: Vec<String> = rows
+                        .iter()
+                        .map(|row| {
+                            let row: Vec<String> = row
+                                .iter()
+                                .map(|cell| escape_separators(cell, &[',', ';']))
+                                .collect();
+                            row.join(", ")
+                        })
+                        .collect();
+                    self.base.record(&cells.join("; "));
+                    io_handl!(writer.finish_with(
+                        stdout,
+                        self.base
+                            .answered_line(self.theme, &format!("{} rows", rows.len()))
+                    ));
+                    return Ok(rows);
+                }
+                Event::Key(Key::Ctrl('n') | Key::Insert) => {
+                    let at = if len == 0 { 0 } else { view.cursor + 1 };
+                    rows.insert(at, vec![String::new(); cols]);
+                    view.select(at, rows.len());
+                    col = 0;
+                }
+                Event::Key(Key::Ctrl('x') | Key::Delete) if len > 0 => {
+                    rows.remove(view.cursor);
+                    view.scroll(rows.len());
+                }
+                Event::Key(Key::Char('\n')) if len > 0 => {
+                    editing = Some(LineEditor::with_value(rows[view.cursor][col].clone()));
+                }
+                Event::Key(Key::Char(ch)) if len > 0 && !ch.is_control() => {
+                    editing = Some(LineEditor::with_value(ch.to_string()));
+                }
+                Event::Key(Key::Char('\t')) => {
+                    col += 1;
+                    if col == cols {
+                        col = 0;
+                        view.down(len);
+                    }
+                }
+                Event::Key(Key::BackTab) => {
+                    if col == 0 {
+                        col = cols - 1;
+                        view.up(len);
+                    } else {
+                        col -= 1;
+                    }
+                }
+                Event::Key(Key::Left) => col = col.saturating_sub(1),
+                Event::Key(Key::Right) => col = (col + 1).min(cols - 1),
+                Event::Key(Key::Up) => view.up(len),
+                Event::Key(Key::Down) => view.down(len),
+                Event::Key(Key::PageUp) => view.page_up(len),
+                Event::Key(Key::PageDown) => view.page_down(len),
+                Event::Key(Key::Home) => view.home(len),
+                Event::Key(Key::End) => view.end(len),
+                _ => continue,
+            };
+
+            err = None;
+            update!();
+        }
+
+        Ok(rows)
+    }
+
+    pub fn prompt_handled<R: Read, W: Write>(
+        &mut self,
+        stdin: &mut Events<R>,
+        stdout: &mut W,
+        go_back: Option<bool>,
+    ) -> Result<Vec<Vec<String>>, PromptError> {
+        match &self.base.prompt_type {
+            PromptType::Table => {}
+            _ => {
+                return Err(PromptError::Custom(String::from(
+                    "Prompt struct only can be Table",
+                )));
+            }
+        };
+
+        if self.columns.is_empty() {
+            return Err(PromptError::Custom(String::from(
+                "The table has no columns",
+            )));
+        }
+
+        self.prompt(stdin, stdout, go_back)
+    }
+}
@@ -1,13 +1,14 @@
 use std::fmt::Debug;
 use std::io::{self, Read, Write};
 
-use termion::clear::CurrentLine;
-use termion::cursor::{DetectCursorPos, Goto, Left};
 use termion::event::{Event, Key};
 use termion::input::Events;
 
 use crate::themes::FormatTheme;
-use crate::{io_handl, Color, MyFromStr, PromptBase, PromptError, PromptType};
+use crate::{
+    ansi, io_handl, is_resize, mask_password, Frame, FrameWriter, LineEditor, PromptBase,
+    PromptError, PromptType, RedactInput,
+};
 
 #[derive(Debug)]
 pub struct TextPrompt<'a, T: FormatTheme> {
@@ -25,136 +26,113 @@ impl<'a, T: FormatTheme> TextPrompt<'a, T> {
         self.base.write_default(self.theme, stdout)
     }
 
-    pub fn prompt<R: Read, W: Write>(
+    /// Read a line, checking it against the prompt type when `validate`
+    /// is set
+    fn read<R: Read, W: Write>(
         &mut self,
         stdin: &mut Events<R>,
         stdout: &mut W,
         go_back: Option<bool>,
+        validate: bool,
     ) -> Result<String, PromptError> {
-        let go_back = match go_back {
-            Some(v) => v,
-            None => true,
-        };
-
-        let mouse_pos = io_handl!(DetectCursorPos::cursor_pos(stdout));
-
-        let end_line = match &self.base.line {
-            None => {
-                self.base.line = Option::Some(mouse_pos.1);
-                mouse_pos.1 + 1
-            }
-            Some(line) => {
-                io_handl!(write!(stdout, "{}", Goto(0, *line)));
-                if go_back {
-                    mouse_pos.1
-                } else {
-                    *line + 1
-                }
+        let prompt_type = &self.base.prompt_type;
+        if let Some(answer) = self.base.resolve(self.theme, stdin, stdout, |line| {
+            if validate {
+                prompt_type.validate(line)?;
             }
-        };
-
-        io_handl!(self.write_text(stdout));
-        io_handl!(self.write_default(stdout));
+            Ok(line.to_string())
+        }) {
+            return answer;
+        }
 
-        let mut pre_data = String::new();
-        let mut post_data = String::new();
+        io_handl!(self.base.start_line(stdout, go_back.unwrap_or(true)));
 
-        macro_rules! get_data {
-            () => {
-                format!("{}{}", pre_data, post_data)
-            };
-        }
+        let _redact = (self.base.prompt_type == PromptType::Password).then(RedactInput::new);
+        let mut writer = FrameWriter::new();
+        let mut editor = LineEditor::with_value(self.base.initial.clone().unwrap_or_default());
+        let mut err: Option<String> = None;
 
         macro_rules! update {
             () => {
-                io_handl!(self.write_text(stdout));
-                io_handl!(write!(stdout, "{}", Color::Cyan.a(get_data!())));
-                if post_data.len() >= 1 {
-                    io_handl!(write!(stdout, "{}", Left(post_data.len() as u16)));
+                let mut line = self.theme.format_prompt(self.base);
+                let mut back = editor.cursor_back();
+                if editor.is_empty() {
+                    let placeholder = self.base.format_placeholder(self.theme);
+                    back += ansi::width(&placeholder);
+                    line.push_str(&self.theme.format_default(self.base));
+                    line.push_str(&placeholder);
+                } else {
+                    let value = match self.base.prompt_type {
+                        PromptType::Password => mask_password(&editor.value()),
+                        _ => editor.value(),
+                    };
+                    line.push_str(&self.theme.format_input(&value));
+                }
+
+                let mut frame = Frame::new();
+                frame.push(line);
+                frame.cursor_here(back);
+                if let Some(err) = &err {
+                    frame.push(self.theme.format_error(err));
                 }
-                io_handl!(stdout.flush());
+                self.base.add_help(self.theme, &mut frame);
+                io_handl!(writer.draw(stdout, &frame));
             };
         }
 
+        update!();
+
         for c in stdin {
             let evt = c.unwrap();
+            if is_resize(&evt) {
+                io_handl!(writer.redraw(stdout));
+                continue;
+            }
+
             match evt {
                 Event::Key(Key::Ctrl('c') | Key::Esc) => {
-                    update!();
-                    io_handl!(write!(stdout, "{}\n", Color::Red.a("<cancelled>")));
-                    io_handl!(stdout.flush());
+                    io_handl!(writer.finish_with(stdout, self.theme.format_cancelled(self.base)));
                     return Err(PromptError::KeyboardInterrupt);
                 }
                 Event::Key(Key::Char('\n')) => {
-                    if get_data!().len() == 0 && self.base.default.is_some() {
-                        post_data = String::new();
-                        pre_data = match &self.base.default {
-                            None => unreachable!(),
-                            Some(default) => default.clone(),
-                        };
-                        update!();
+                    if editor.is_empty() {
+                        if let Some(default) = &self.base.default {
+                            editor.set(default.clone());
+                        }
                     }
 
-                    io_handl!(write!(
-                        stdout,
-                        "{}\n{}",
-                        Goto(9999, end_line - 1),
-                        CurrentLine
-                    ));
-                    io_handl!(stdout.flush());
-                    break;
-                }
-                Event::Key(Key::Char(ch)) => {
-                    pre_data.push(ch);
-                    update!();
-                }
-                Event::Key(Key::Left) => match pre_data.pop() {
-                    Option::None => {}
-                    Option::Some(ch) => {
-                        post_data = format!("{}{}", ch, post_data);
-                        update!();
-                    }
-                },
-                Event::Key(Key::Right) => {
-                    if post_data.len() >= 1 {
-                        let (ch, tail) = post_data.split_at(1);
-                        pre_data.push_str(ch);
-                        post_data = String::from(tail);
-                        update!();
-                    } else {
-                        pre_data.push_str(post_data.as_str());
-                        post_data = String::new();
-                        update!();
-                    }
-                }
-                Event::Key(Key::Backspace) => match pre_data.pop() {
-                    Option::None => {}
-                    Option::Some(_) => {
-                        update!();
-                    }
-                },
-                Event::Key(Key::Delete) => {
-                    if post_data.len() >= 1 {
-                        let (_, tail) = post_data.split_at(1);
-                        post_data = String::from(tail);
-                        update!();
+                    let value = editor.value();
+                    if validate {
+                        if let Err(msg) = self.base.prompt_type.validate(&value) {
+                            err = Some(msg);
+                            update!();
+                            continue;
+                        }
                     }
+
+                    self.base.record(&value);
+                    let line = self.base.answered_line(self.theme, &value);
+                    io_handl!(writer.finish_with(stdout, line));
+                    return Ok(value);
                 }
-                Event::Key(Key::Home | Key::PageUp) => {
-                    post_data = get_data!();
-                    pre_data = String::new();
-                    update!();
-                }
-                Event::Key(Key::End | Key::PageDown) => {
-                    pre_data = get_data!();
-                    post_data = String::new();
+                Event::Key(key) if editor.handle(&key) => {
+                    err = None;
                     update!();
                 }
                 _ => {}
             };
         }
 
-        Ok(get_data!())
+        Ok(editor.value())
+    }
+
+    pub fn prompt<R: Read, W: Write>(
+        &mut self,
+        stdin: &mut Events<R>,
+        stdout: &mut W,
+        go_back: Option<bool>,
+    ) -> Result<String, PromptError> {
+        self.read(stdin, stdout, go_back, false)
     }
 
     pub fn prompt_handled<R: Read, W: Write>(
@@ -164,48 +142,14 @@ impl<'a, T: FormatTheme> TextPrompt<'a, T> {
         go_back: Option<bool>,
     ) -> Result<String, PromptError> {
         match &self.base.prompt_type {
-            PromptType::String | PromptType::Bool => {}
+            PromptType::String | PromptType::Password | PromptType::Bool => {}
             _ => {
                 return Err(PromptError::Custom(String::from(
-                    "Prompt struct only can be String, Bool, FilePath or FilePathExisting",
+                    "Prompt struct only can be String, Password, Bool, FilePath or FilePathExisting",
                 )));
             }
         };
 
-        macro_rules! send_err {
-            ($msg:expr) => {
-                io_handl!(write!(stdout, "{}{}", Left(99), CurrentLine));
-                io_handl!(write!(stdout, "{}{}", CurrentLine, Color::Red.a($msg)));
-            };
-        }
-
-        'prompt: loop {
-            let prompted = self.prompt(stdin, stdout, go_back.clone());
-            match prompted {
-                Ok(expr) => match &self.base.prompt_type {
-                    PromptType::String => {
-                        if expr.len() == 0 {
-                            send_err!("The text should contain 1 character or more");
-                            continue 'prompt;
-                        }
-
-                        return Ok(expr);
-                    }
-                    PromptType::Bool => {
-                        let parsed = <bool as MyFromStr>::from_str(expr.as_str());
-                        if parsed.is_ok() {
-                            return Ok(expr);
-                        }
-
-                        send_err!("The provided value is invalid. (yes|y|true|no|n|false)");
-                        continue 'prompt;
-                    }
-                    _ => {
-                        unreachable!();
-                    }
-                },
-                Err(err) => return Err(err),
-            }
-        }
+        self.read(stdin, stdout, go_back, true)
     }
 }
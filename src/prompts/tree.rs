@@ -0,0 +1,469 @@
+use std::io::{Read, Write};
+
+use termion::event::{Event, Key};
+use termion::input::Events;
+
+use crate::themes::{FormatTheme, Glyphs};
+use crate::{
+    escape_separators, io_handl, is_resize, split_unescaped, unescape, Frame, FrameWriter,
+    ListView, PromptBase, PromptError, PromptType,
+};
+
+/// Indexes from a root node down to a node
+pub type TreePath = Vec<usize>;
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TreeNode {
+    pub label: String,
+    pub children: Vec<TreeNode>,
+    pub expanded: bool,
+    /// Only used by leaves, parents derive it from their children
+    pub checked: bool,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CheckState {
+    Checked,
+    Unchecked,
+    Partial,
+}
+
+impl TreeNode {
+    pub fn new<S: Into<String>>(label: S, children: Vec<TreeNode>) -> Self {
+        TreeNode {
+            label: label.into(),
+            children,
+            expanded: false,
+            checked: false,
+        }
+    }
+
+    pub fn leaf<S: Into<String>>(label: S) -> Self {
+        TreeNode::new(label, vec![])
+    }
+
+    /// Build a forest from paths such as `crates/core/src`, splitting on `sep`
+    pub fn from_paths<S: AsRef<str>>(paths: &[S], sep: char) -> Vec<TreeNode> {
+        let mut roots: Vec<TreeNode> = vec![];
+        for path in paths {
+            let mut level = &mut roots;
+            for part in path.as_ref().split(sep).filter(|p| !p.is_empty()) {
+                let idx = match level.iter().position(|n| n.label == part) {
+                    Some(idx) => idx,
+                    None => {
+                        level.push(TreeNode::leaf(part));
+                        level.len() - 1
+                    }
+                };
+                level = &mut level[idx].children;
+            }
+        }
+
+        roots
+    }
+
+    pub fn is_leaf(&self) -> bool {
+        self.children.is_empty()
+    }
+
+    pub fn state(&self) -> CheckState {
+        if self.is_leaf() {
+            return if self.checked {
+                CheckState::Checked
+            } else {
+                CheckState::Unchecked
+            };
+        }
+
+        let mut states = self.children.iter().map(TreeNode::state);
+        let first = states.next().unwrap_or(CheckState::Unchecked);
+        if states.all(|s| s == first) {
+            first
+        } else {
+            CheckState::Partial
+        }
+    }
+
+    /// Check or uncheck the node and everything under it
+    pub fn set_checked(&mut self, checked: bool) {
+        self.checked = checked;
+        for child in &mut self.children {
+            child.set_checked(checked);
+        }
+    }
+
+    fn checked_leaves(&self, path: &mut TreePath, out: &mut Vec<TreePath>) {
+        if self.is_leaf() {
+            if self.checked {
+                out.push(path.clone());
+            }
+            return;
+        }
+
+        for (i, child) in self.children.iter().enumerate() {
+            path.push(i);
+            child.checked_leaves(path, out);
+            path.pop();
+        }
+    }
+}
+
+fn node_at<'n>(nodes: &'n [TreeNode], path: &[usize]) -> &'n TreeNode {
+    let mut node = &nodes[path[0]];
+    for i in &path[1..] {
+        node = &node.children[*i];
+    }
+    node
+}
+
+fn node_at_mut<'n>(nodes: &'n mut [TreeNode], path: &[usize]) -> &'n mut TreeNode {
+    let mut node = &mut nodes[path[0]];
+    for i in &path[1..] {
+        node = &mut node.children[*i];
+    }
+    node
+}
+
+/// A visible row of the tree
+#[derive(Clone, Debug)]
+struct TreeRow {
+    path: TreePath,
+    guides: String,
+}
+
+fn flatten(
+    nodes: &[TreeNode],
+    glyphs: &Glyphs,
+    path: &mut TreePath,
+    prefix: &str,
+    out: &mut Vec<TreeRow>,
+) {
+    for (i, node) in nodes.iter().enumerate() {
+        let last = i + 1 == nodes.len();
+        path.push(i);
+
+        let (guide, next) = if path.len() == 1 {
+            (String::new(), String::new())
+        } else if last {
+            (
+                format!("{}{}", prefix, glyphs.tree_last),
+                format!("{}{}", prefix, glyphs.tree_empty),
+            )
+        } else {
+            (
+                format!("{}{}", prefix, glyphs.tree_branch),
+                format!("{}{}", prefix, glyphs.tree_pipe),
+            )
+        };
+
+        out.push(TreeRow {
+            path: path.clone(),
+            guides: guide,
+        });
+        if node.expanded {
+            flatten(&node.children, glyphs, path, &next, out);
+        }
+
+        path.pop();
+    }
+}
+
+/// Pick a node of a tree. Right and Left expand and collapse, with
+/// `multi` Space toggles checkboxes and the checked leaves are returned.
+#[derive(Debug)]
+pub struct TreePrompt<'a, T: FormatTheme> {
+    pub base: &'a mut PromptBase,
+    pub theme: &'a T,
+    pub nodes: Vec<TreeNode>,
+    pub multi: bool,
+    /// Rows shown at once, `0` shows every row
+    pub page_size: usize,
+}
+
+impl<'a, T: FormatTheme> TreePrompt<'a, T> {
+    fn format_row(&self, row: &TreeRow, glyphs: &Glyphs) -> String {
+        let node = node_at(&self.nodes, &row.path);
+        let marker = if node.is_leaf() {
+            " "
+        } else if node.expanded {
+            glyphs.expanded
+        } else {
+            glyphs.collapsed
+        };
+
+        let check = if self.multi {
+            format!("{} ", self.theme.format_checkbox(node.state()))
+        } else {
+            String::new()
+        };
+
+        format!(
+            "{}{} {}{}",
+            self.theme.format_hint(&row.guides),
+            marker,
+            check,
+            node.label
+        )
+    }
+
+    /// Labels from the root to the node at `path` joined with `/`
+    fn path_labels(&self, path: &[usize]) -> String {
+        let labels: Vec<&str> = (1..=path.len())
+            .map(|depth| node_at(&self.nodes, &path[..depth]).label.as_str())
+            .collect();
+        labels.join("/")
+    }
+
+    /// `path_labels` written in an answer, with `/` and `,` in the labels
+    /// escaped
+    fn answer_path(&self, path: &[usize]) -> String {
+        let labels: Vec<String> = (1..=path.len())
+            .map(|depth| {
+                escape_separators(&node_at(&self.nodes, &path[..depth]).label, &['/', ','])
+            })
+            .collect();
+        labels.join("/")
+    }
+
+    /// Labels joined with `/`, e.g. `src/main.rs`. A `\` escapes a `/` or
+    /// comma in a label.
+    fn find_path(&self, labels: &str) -> Result<TreePath, String> {
+        let mut path = vec![];
+        let mut nodes = &self.nodes;
+
+        for label in split_unescaped(labels, '/') {
+            let label = unescape(label.trim(), &['/', ',']);
+            let idx = nodes
+                .iter()
+                .position(|node| node.label == label)
+                .ok_or_else(|| format!("{} is not in the tree", labels))?;
+            path.push(idx);
+            nodes = &nodes[idx].children;
+        }
+
+        Ok(path)
+    }
+
+    /// Paths separated by commas. With `multi` they must be leaves, and an
+    /// empty line keeps the checked ones.
+    fn parse_paths(&self, line: &str) -> Result<Vec<TreePath>, String> {
+        let mut paths = vec![];
+        if line.trim().is_empty() && self.multi {
+            for (i, node) in self.nodes.iter().enumerate() {
+                node.checked_leaves(&mut vec![i], &mut paths);
+            }
+        } else if !line.trim().is_empty() {
+            for labels in split_unescaped(line, ',') {
+                let path = self.find_path(labels)?;
+                if self.multi && !node_at(&self.nodes, &path).is_leaf() {
+                    return Err(format!("{} is not a leaf", labels.trim()));
+                }
+                paths.push(path);
+            }
+        }
+
+        match paths.len() {
+            0 if self.multi => Err(String::from("Check at least one item")),
+            1 => Ok(paths),
+            _ if self.multi => Ok(paths),
+            _ => Err(String::from("Pick one item")),
+        }
+    }
+
+    pub fn prompt<R: Read, W: Write>(
+        &mut self,
+        stdin: &mut Events<R>,
+        stdout: &mut W,
+        go_back: Option<bool>,
+    ) -> Result<Vec<TreePath>, PromptError> {
+        if let Some(answer) = self
+            .base
+            .resolve(self.theme, stdin, stdout, |line| self.parse_paths(line))
+        {
+            return answer;
+        }
+
+        io_handl!(self.base.start_line(stdout, go_back.unwrap_or(true)));
+
+        let mut writer = FrameWriter::new();
+        let mut view = ListView::new(self.page_size);
+        let glyphs = self.theme.glyphs();
+        let mut rows: Vec<TreeRow> = vec![];
+        flatten(&self.nodes, &glyphs, &mut vec![], "", &mut rows);
+
+        macro_rules! update {
+            ($err:expr) => {
+                rows.clear();
+                flatten(&self.nodes, &glyphs, &mut vec![], "", &mut rows);
+                view.scroll(rows.len());
+
+                let hint = if self.multi {
+                    String::from("(space to check, enter to confirm)")
+                } else {
+                    format!("({} to collapse or expand)", glyphs.left_right)
+                };
+                let lines: Vec<String> = rows
+                    .iter()
+                    .map(|row| self.format_row(row, &glyphs))
+                    .collect();
+                let mut frame = Frame::new();
+                frame.push(format!(
+                    "{}{}",
+                    self.theme.format_prompt(self.base),
+                    self.theme.format_hint(&hint)
+                ));
+                for line in view.render(self.theme, &lines) {
+                    frame.push(line);
+                }
+                if let Some(err) = $err {
+                    frame.push(self.theme.format_error(err));
+                }
+                self.base.add_help(self.theme, &mut frame);
+                io_handl!(writer.draw(stdout, &frame));
+            };
+            () => {
+                update!(Option::<&str>::None)
+            };
+        }
+
+        macro_rules! finish {
+            ($line:expr) => {
+                io_handl!(writer.finish_with(stdout, $line));
+            };
+        }
+
+        update!();
+
+        for c in stdin {
+            let evt = c.unwrap();
+            if is_resize(&evt) {
+                io_handl!(writer.redraw(stdout));
+                continue;
+            }
+            let len = rows.len();
+            let current = rows.get(view.cursor).map(|row| row.path.clone());
+
+            match evt {
+                Event::Key(Key::Ctrl('c') | Key::Esc) => {
+                    finish!(self.theme.format_cancelled(self.base));
+                    return Err(PromptError::KeyboardInterrupt);
+                }
+                Event::Key(Key::Char('\n')) if self.multi => {
+                    let mut checked = vec![];
+                    for (i, node) in self.nodes.iter().enumerate() {
+                        node.checked_leaves(&mut vec![i], &mut checked);
+                    }
+
+                    if checked.is_empty() {
+                        update!(Some("Check at least one item"));
+                        continue;
+                    }
+
+                    let labels: Vec<&str> = checked
+                        .iter()
+                        .map(|path| node_at(&self.nodes, path).label.as_str())
+                        .collect();
+                    let paths: Vec<String> =
+                        checked.iter().map(|path| self.answer_path(path)).collect();
+                    self.base.record(&paths.join(", "));
+                    finish!(self.base.answered_line(self.theme, &labels.join(", ")));
+                    return Ok(checked);
+                }
+                Event::Key(Key::Char('\n')) => {
+                    let path = match current {
+                        Some(path) => path,
+                        None => continue,
+                    };
+                    let labels = self.path_labels(&path);
+                    self.base.record(&self.answer_path(&path));
+                    finish!(self.base.answered_line(self.theme, &labels));
+                    return Ok(vec![path]);
+                }
+                Event::Key(Key::Char(' ')) if self.multi => {
+                    if let Some(path) = current {
+                        let node = node_at_mut(&mut self.nodes, &path);
+                        let checked = node.state() != CheckState::Checked;
+                        node.set_checked(checked);
+                    }
+                }
+                Event::Key(Key::Right) => {
+                    if let Some(path) = current {
+                        let node = node_at_mut(&mut self.nodes, &path);
+                        if node.is_leaf() {
+                            continue;
+                        } else if node.expanded {
+                            view.down(len);
+                        } else {
+                            node.expanded = true;
+                        }
+                    }
+                }
+                Event::Key(Key::Left) => {
+                    if let Some(path) = current {
+                        let node = node_at_mut(&mut self.nodes, &path);
+                        if node.expanded {
+                            node.expanded = false;
+                        } else if path.len() > 1 {
+                            let parent = &path[..path.len() - 1];
+                            if let Some(idx) = rows.iter().position(|row| row.path == parent) {
+                                view.select(idx, len);
+                            }
+                        }
+                    }
+                }
+                Event::Key(Key::Up) => view.up(len),
+                Event::Key(Key::Down) => view.down(len),
+                Event::Key(Key::PageUp) => view.page_up(len),
+                Event::Key(Key::PageDown) => view.page_down(len),
+                Event::Key(Key::Home) => view.home(len),
+                Event::Key(Key::End) => view.end(len),
+                _ => continue,
+            };
+
+            update!();
+        }
+
+        Ok(vec![])
+    }
+
+    pub fn prompt_handled<R: Read, W: Write>(
+        &mut self,
+        stdin: &mut Events<R>,
+        stdout: &mut W,
+        go_back: Option<bool>,
+    ) -> Result<Vec<TreePath>, PromptError> {
+        match &self.base.prompt_type {
+            PromptType::Tree => {}
+            _ => {
+                return Err(PromptError::Custom(String::from(
+                    "Prompt struct only can be Tree",
+                )));
+            }
+        };
+
+        if self.nodes.is_empty() {
+            return Err(PromptError::Custom(String::from("The tree has no nodes")));
+        }
+
+        self.prompt(stdin, stdout, go_back)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn tri_state() {
+        let mut nodes = TreeNode::from_paths(&["apps/web", "apps/api", "libs/ui"], '/');
+        assert_eq!(nodes.len(), 2);
+        assert_eq!(nodes[0].children.len(), 2);
+
+        nodes[0].children[1].checked = true;
+        assert_eq!(nodes[0].state(), CheckState::Partial);
+
+        nodes[0].set_checked(true);
+        assert_eq!(nodes[0].state(), CheckState::Checked);
+        assert_eq!(nodes[1].state(), CheckState::Unchecked);
+    }
+}
@@ -0,0 +1,163 @@
+use std::io::{self, Read};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+
+use termion::event::Event;
+
+/// Sequence handed to the events iterator when the terminal is resized.
+/// Termion does not know it and reports it as `Event::Unsupported`.
+pub const RESIZE_SEQ: &[u8] = b"\x1b[8;0;0t";
+
+static RESIZED: AtomicBool = AtomicBool::new(false);
+/// Readers watching for resizes and the `SIGWINCH` action there was before
+static WATCH: Mutex<(usize, Option<libc::sigaction>)> = Mutex::new((0, None));
+
+extern "C" fn on_resize(_: libc::c_int) {
+    RESIZED.store(true, Ordering::Relaxed);
+}
+
+/// Listen for `SIGWINCH` until a matching `unwatch_resize`. The handler is
+/// installed without `SA_RESTART` so a blocked read returns and the prompt
+/// can redraw right away, which makes any other blocking call of the
+/// program fail with `EINTR` on a resize meanwhile.
+pub fn watch_resize() {
+    let mut watch = WATCH.lock().unwrap();
+    if watch.0 == 0 {
+        unsafe {
+            let mut action: libc::sigaction = std::mem::zeroed();
+            action.sa_sigaction = on_resize as extern "C" fn(libc::c_int) as libc::sighandler_t;
+            libc::sigemptyset(&mut action.sa_mask);
+
+            let mut previous: libc::sigaction = std::mem::zeroed();
+            if libc::sigaction(libc::SIGWINCH, &action, &mut previous) == 0 {
+                watch.1 = Some(previous);
+            }
+        }
+    }
+    watch.0 += 1;
+}
+
+/// Stop listening once every `watch_resize` has its match, putting back the
+/// handler the program had before
+pub fn unwatch_resize() {
+    let mut watch = WATCH.lock().unwrap();
+    if watch.0 == 0 {
+        return;
+    }
+
+    watch.0 -= 1;
+    if watch.0 == 0 {
+        if let Some(previous) = watch.1.take() {
+            unsafe {
+                libc::sigaction(libc::SIGWINCH, &previous, std::ptr::null_mut());
+            }
+        }
+    }
+}
+
+/// Whether the terminal was resized since the last call
+pub fn take_resized() -> bool {
+    RESIZED.swap(false, Ordering::Relaxed)
+}
+
+pub fn is_resize(evt: &Event) -> bool {
+    matches!(evt, Event::Unsupported(seq) if seq == RESIZE_SEQ)
+}
+
+/// Input that yields `RESIZE_SEQ` when the terminal is resized, so the
+/// prompts see resizes as events. Used by `create_events!`.
+pub struct ResizeReader<R: Read> {
+    inner: R,
+    pending: Vec<u8>,
+    watching: bool,
+}
+
+impl<R: Read> ResizeReader<R> {
+    /// Reader that only reports resizes while something else calls
+    /// `watch_resize`
+    pub fn new(inner: R) -> Self {
+        ResizeReader {
+            inner,
+            pending: vec![],
+            watching: false,
+        }
+    }
+
+    /// Reader that watches for resizes itself. This installs a process wide
+    /// `SIGWINCH` handler, see `watch_resize`, until the reader is dropped.
+    pub fn watching(inner: R) -> Self {
+        watch_resize();
+        ResizeReader {
+            inner,
+            pending: vec![],
+            watching: true,
+        }
+    }
+}
+
+impl<R: Read> Drop for ResizeReader<R> {
+    fn drop(&mut self) {
+        if self.watching {
+            unwatch_resize();
+        }
+    }
+}
+
+impl<R: Read> Read for ResizeReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        loop {
+            if self.pending.is_empty() && take_resized() {
+                self.pending = RESIZE_SEQ.to_vec();
+            }
+
+            if !self.pending.is_empty() {
+                let len = buf.len().min(self.pending.len());
+                buf[..len].copy_from_slice(&self.pending[..len]);
+                self.pending.drain(..len);
+                return Ok(len);
+            }
+
+            match self.inner.read(buf) {
+                Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+                res => return res,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TermRead;
+
+    #[test]
+    pub fn resize_event() {
+        let mut input = ResizeReader::new(&b"a"[..]);
+        input.pending = RESIZE_SEQ.to_vec();
+
+        let events: Vec<Event> = input.events().map(|evt| evt.unwrap()).collect();
+        assert!(is_resize(&events[0]));
+        assert!(!is_resize(&events[1]));
+    }
+
+    #[test]
+    pub fn handler_put_back() {
+        let handler = || unsafe {
+            let mut action: libc::sigaction = std::mem::zeroed();
+            libc::sigaction(libc::SIGWINCH, std::ptr::null(), &mut action);
+            action.sa_sigaction
+        };
+        let before = handler();
+
+        let first = ResizeReader::watching(&b""[..]);
+        let second = ResizeReader::watching(&b""[..]);
+        assert_eq!(
+            handler(),
+            on_resize as extern "C" fn(libc::c_int) as libc::sighandler_t
+        );
+        drop(first);
+        assert_ne!(handler(), before);
+        drop(second);
+        assert_eq!(handler(), before);
+    }
+}
@@ -0,0 +1,443 @@
+use std::collections::VecDeque;
+use std::fs;
+use std::io::{self, Read};
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+
+use crate::Answers;
+
+static SESSION: Mutex<Option<Session>> = Mutex::new(None);
+static REDACT: AtomicBool = AtomicBool::new(false);
+
+/// Input read by the prompts and the answers they gave, to reproduce a run
+/// with `Session::replay` or to turn it into `Answers`.
+///
+/// Input is kept in the chunks it was read in, so a lone `Esc` is not
+/// replayed together with the next key.
+///
+/// Password prompts and secret values are not kept: the characters typed
+/// into them are recorded as `*` and their answers are left out, so they
+/// need an answer from `Answers` to be replayed.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Session {
+    pub input: Vec<Vec<u8>>,
+    /// Answers of the prompts with a `key`, in the order they were given
+    pub answers: Vec<(String, String)>,
+}
+
+/// `bytes` as a quoted string, bytes out of printable ASCII written as `\xNN`
+fn quote(bytes: &[u8]) -> String {
+    let mut out = String::from("\"");
+    for byte in bytes {
+        match byte {
+            b'"' => out.push_str("\\\""),
+            b'\\' => out.push_str("\\\\"),
+            b' '..=b'~' => out.push(*byte as char),
+            _ => out.push_str(&format!("\\x{:02x}", byte)),
+        }
+    }
+    out.push('"');
+    out
+}
+
+/// Read a string written by `quote` from the start of `s`, returning the
+/// rest of it
+fn unquote(s: &str) -> Option<(Vec<u8>, &str)> {
+    let s = s.trim_start().strip_prefix('"')?;
+    let mut bytes = vec![];
+    let mut iter = s.bytes().enumerate();
+
+    while let Some((i, byte)) = iter.next() {
+        match byte {
+            b'"' => return Some((bytes, &s[i + 1..])),
+            b'\\' => match iter.next()?.1 {
+                b'x' => {
+                    let hex = s.get(i + 2..i + 4)?;
+                    bytes.push(u8::from_str_radix(hex, 16).ok()?);
+                    iter.nth(1)?;
+                }
+                other => bytes.push(other),
+            },
+            other => bytes.push(other),
+        }
+    }
+
+    None
+}
+
+impl Session {
+    pub fn new() -> Self {
+        Session::default()
+    }
+
+    /// One line per input chunk and answer:
+    ///
+    /// ```text
+    /// input "\x1b[A"
+    /// answer "name" "value"
+    /// ```
+    pub fn to_text(&self) -> String {
+        let mut out = String::new();
+        for chunk in &self.input {
+            out.push_str(&format!("input {}\n", quote(chunk)));
+        }
+        for (key, answer) in &self.answers {
+            out.push_str(&format!(
+                "answer {} {}\n",
+                quote(key.as_bytes()),
+                quote(answer.as_bytes())
+            ));
+        }
+
+        out
+    }
+
+    pub fn from_text(text: &str) -> Result<Self, String> {
+        let mut session = Session::new();
+
+        for (i, line) in text.lines().enumerate() {
+            let invalid = || format!("Invalid session line {}: {}", i + 1, line);
+
+            if let Some(rest) = line.strip_prefix("input ") {
+                let (chunk, _) = unquote(rest).ok_or_else(invalid)?;
+                session.input.push(chunk);
+            } else if let Some(rest) = line.strip_prefix("answer ") {
+                let (key, rest) = unquote(rest).ok_or_else(invalid)?;
+                let (answer, _) = unquote(rest).ok_or_else(invalid)?;
+                let key = String::from_utf8(key).map_err(|_| invalid())?;
+                let answer = String::from_utf8(answer).map_err(|_| invalid())?;
+                session.answers.push((key, answer));
+            } else if !line.trim().is_empty() {
+                return Err(invalid());
+            }
+        }
+
+        Ok(session)
+    }
+
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        fs::write(path, self.to_text())
+    }
+
+    pub fn load<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let text = fs::read_to_string(path)?;
+        Session::from_text(&text).map_err(|msg| io::Error::new(io::ErrorKind::InvalidData, msg))
+    }
+
+    /// The answers given, ready for `set_answers` or `Answers::save`
+    pub fn to_answers(&self) -> Answers {
+        let mut answers = Answers::new();
+        for (key, answer) in &self.answers {
+            answers.insert(key.clone(), answer.clone());
+        }
+
+        answers
+    }
+
+    /// Input to build the events of a new run from, e.g.
+    /// `TermRead::events(session.replay())`
+    pub fn replay(&self) -> Replay {
+        Replay {
+            chunks: self.input.iter().cloned().collect(),
+        }
+    }
+}
+
+/// Start keeping what `Recorder`s read and the answers of the prompts
+pub fn start_recording() {
+    *SESSION.lock().unwrap() = Some(Session::new());
+}
+
+/// Stop recording, returning what was recorded since `start_recording`
+pub fn stop_recording() -> Option<Session> {
+    SESSION.lock().unwrap().take()
+}
+
+/// Keep `answer` for `key` when recording, replacing an earlier one
+pub fn record_answer(key: &str, answer: &str) {
+    if let Some(session) = SESSION.lock().unwrap().as_mut() {
+        session.answers.retain(|(other, _)| other != key);
+        session
+            .answers
+            .push((String::from(key), String::from(answer)));
+    }
+}
+
+/// Drop the answer kept for `key`, for one that can not be recorded
+pub fn forget_answer(key: &str) {
+    if let Some(session) = SESSION.lock().unwrap().as_mut() {
+        session.answers.retain(|(other, _)| other != key);
+    }
+}
+
+/// Input read while this is alive is recorded with its characters replaced
+/// by `*`, for prompts reading a password or a secret. Dropping it goes
+/// back to what was in effect before, so guards can be nested.
+pub struct RedactInput {
+    previous: bool,
+}
+
+impl RedactInput {
+    pub fn new() -> Self {
+        RedactInput {
+            previous: REDACT.swap(true, Ordering::Relaxed),
+        }
+    }
+}
+
+impl Default for RedactInput {
+    fn default() -> Self {
+        RedactInput::new()
+    }
+}
+
+impl Drop for RedactInput {
+    fn drop(&mut self) {
+        REDACT.store(self.previous, Ordering::Relaxed);
+    }
+}
+
+/// Where a `Recorder` is in the input, so escape sequences split across
+/// reads are not taken for typed characters
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+enum Scan {
+    #[default]
+    Text,
+    Escape,
+    Sequence,
+}
+
+/// `bytes` with every typed character replaced by `*`. Keys such as enter,
+/// backspace and arrows are kept so the input still replays the same way.
+fn redact(scan: &mut Scan, bytes: &[u8]) -> Vec<u8> {
+    let mut out = vec![];
+    for byte in bytes {
+        match (*scan, byte) {
+            (_, 0x1b) => {
+                *scan = Scan::Escape;
+                out.push(*byte);
+            }
+            (Scan::Escape, b'[' | b'O') => {
+                *scan = Scan::Sequence;
+                out.push(*byte);
+            }
+            (Scan::Escape, _) | (Scan::Sequence, 0x40..=0x7e) => {
+                *scan = Scan::Text;
+                out.push(*byte);
+            }
+            (Scan::Sequence, _) => out.push(*byte),
+            // One `*` for the first byte of each UTF-8 character
+            (Scan::Text, b' '..=b'~' | 0xc0..) => out.push(b'*'),
+            (Scan::Text, 0x80..=0xbf) => {}
+            (Scan::Text, _) => out.push(*byte),
+        }
+    }
+
+    out
+}
+
+/// Input that keeps what it reads while recording, e.g.
+/// `TermRead::events(Recorder::new(ResizeReader::watching(stdin())))`
+pub struct Recorder<R: Read> {
+    inner: R,
+    scan: Scan,
+}
+
+impl<R: Read> Recorder<R> {
+    pub fn new(inner: R) -> Self {
+        Recorder {
+            inner,
+            scan: Scan::Text,
+        }
+    }
+}
+
+impl<R: Read> Read for Recorder<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let len = self.inner.read(buf)?;
+        let chunk = redact(&mut self.scan, &buf[..len]);
+        if len > 0 {
+            if let Some(session) = SESSION.lock().unwrap().as_mut() {
+                if REDACT.load(Ordering::Relaxed) {
+                    session.input.push(chunk);
+                } else {
+                    session.input.push(buf[..len].to_vec());
+                }
+            }
+        }
+
+        Ok(len)
+    }
+}
+
+/// Recorded input, read back without merging its chunks
+pub struct Replay {
+    chunks: VecDeque<Vec<u8>>,
+}
+
+impl Read for Replay {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let chunk = match self.chunks.front_mut() {
+            Some(chunk) => chunk,
+            None => return Ok(0),
+        };
+
+        let len = buf.len().min(chunk.len());
+        buf[..len].copy_from_slice(&chunk[..len]);
+        chunk.drain(..len);
+        if chunk.is_empty() {
+            self.chunks.pop_front();
+        }
+
+        Ok(len)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        lock_globals, set_answers, themes::DefaultTheme, FinishMode, KeyValuePrompt,
+        ListInputPrompt, PromptBase, PromptType, SortPrompt, TermRead, TreeNode, TreePrompt,
+    };
+    use termion::event::{Event, Key};
+    use termion::input::Events;
+
+    #[test]
+    pub fn round_trip() {
+        let session = Session {
+            input: vec![
+                b"\x1b".to_vec(),
+                b"a\"\\\r".to_vec(),
+                "ñ".as_bytes().to_vec(),
+            ],
+            answers: vec![(String::from("name"), String::from("ñu \"x\""))],
+        };
+
+        let text = session.to_text();
+        assert_eq!(text.lines().next(), Some("input \"\\x1b\""));
+        assert_eq!(Session::from_text(&text), Ok(session.clone()));
+
+        let events: Vec<Event> = session.replay().events().map(|evt| evt.unwrap()).collect();
+        assert_eq!(events[0], Event::Key(Key::Esc));
+        assert_eq!(events[1], Event::Key(Key::Char('a')));
+    }
+
+    #[test]
+    pub fn redacted() {
+        let mut scan = Scan::Text;
+        assert_eq!(redact(&mut scan, "añ1\x7f\r".as_bytes()), b"***\x7f\r");
+
+        // termion reads an arrow in more than one chunk
+        assert_eq!(redact(&mut scan, b"\x1b["), b"\x1b[");
+        assert_eq!(redact(&mut scan, b"D"), b"D");
+        assert_eq!(redact(&mut scan, b"x"), b"*");
+    }
+
+    #[test]
+    pub fn nested_redaction() {
+        let _lock = lock_globals();
+        let outer = RedactInput::new();
+        drop(RedactInput::new());
+        assert!(REDACT.load(Ordering::Relaxed));
+        drop(outer);
+        assert!(!REDACT.load(Ordering::Relaxed));
+    }
+
+    #[test]
+    pub fn secrets_not_recorded() {
+        let mut base = PromptBase {
+            key: Some(String::from("session.secrets")),
+            finish: FinishMode::Clear,
+            ..PromptBase::test(PromptType::KeyValue)
+        };
+
+        let _lock = lock_globals();
+        start_recording();
+        let entries = KeyValuePrompt {
+            base: &mut base,
+            theme: &DefaultTheme,
+            entries: vec![],
+            key_pattern: None,
+            secret_keys: vec![String::from("*_TOKEN")],
+            page_size: 0,
+        }
+        .prompt(
+            &mut (&b"\x0eAPI_TOKEN\rhunter2\r\x04"[..]).events(),
+            &mut Vec::new(),
+            Some(false),
+        );
+        let session = stop_recording().unwrap();
+
+        assert_eq!(entries.unwrap()[0].1, "hunter2");
+        assert!(!session.to_text().contains("hunter"));
+        assert!(session.to_answers().get("session.secrets").is_none());
+    }
+
+    #[test]
+    pub fn recorded_separators() {
+        let base = |key: &str, prompt_type: PromptType| PromptBase {
+            key: Some(format!("session.{}", key)),
+            text: String::from(key),
+            finish: FinishMode::Clear,
+            ..PromptBase::test(prompt_type)
+        };
+        let entries = vec![(String::from("a=b"), String::from("c, d\\e"))];
+        let items = vec![String::from("b, c"), String::from("a")];
+        let mut nodes = vec![TreeNode::new("x,y", vec![TreeNode::leaf("a/b")])];
+        nodes[0].children[0].checked = true;
+
+        let ask = |stdin: &mut Events<&[u8]>| {
+            let mut out = Vec::new();
+            let env = KeyValuePrompt {
+                base: &mut base("env", PromptType::KeyValue),
+                theme: &DefaultTheme,
+                entries: entries.clone(),
+                key_pattern: None,
+                secret_keys: vec![],
+                page_size: 0,
+            }
+            .prompt(stdin, &mut out, Some(false));
+            let order = SortPrompt {
+                base: &mut base("order", PromptType::Sort),
+                theme: &DefaultTheme,
+                items: items.clone(),
+                page_size: 0,
+            }
+            .prompt(stdin, &mut out, Some(false));
+            let tags = ListInputPrompt::<_, String> {
+                base: &mut base("tags", PromptType::List),
+                theme: &DefaultTheme,
+                item_type: PromptType::String,
+                validator: None,
+                min: 0,
+                max: None,
+                chips: false,
+            }
+            .prompt(stdin, &mut out, Some(false));
+            let files = TreePrompt {
+                base: &mut base("files", PromptType::Tree),
+                theme: &DefaultTheme,
+                nodes: nodes.clone(),
+                multi: true,
+                page_size: 0,
+            }
+            .prompt(stdin, &mut out, Some(false));
+
+            (env.unwrap(), order.unwrap(), tags.unwrap(), files.unwrap())
+        };
+
+        // Answered from what was recorded, without reading any input
+        let _lock = lock_globals();
+        start_recording();
+        let typed = ask(&mut (&b"\x04\rx, y\rz\r\r\r"[..]).events());
+        set_answers(stop_recording().unwrap().to_answers());
+        let replayed = ask(&mut (&b""[..]).events());
+        set_answers(Answers::new());
+
+        assert_eq!(typed.0, entries);
+        assert_eq!(typed.2, ["x, y", "z"]);
+        assert_eq!(replayed, typed);
+    }
+}
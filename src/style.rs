@@ -0,0 +1,330 @@
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use crate::{color_level, Color, ColorLevel, Modifier};
+
+/// Foreground, background and a set of modifiers written as one SGR
+/// sequence, e.g. `Style::new().bold().fg(Color::Red)`
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Style {
+    pub fg: Option<Color>,
+    pub bg: Option<Color>,
+    /// Bit `i` set means `Modifier::VARIANTS[i]` is on
+    modifiers: u16,
+}
+
+fn modifier_bit(modifier: Modifier) -> u16 {
+    let idx = Modifier::VARIANTS
+        .iter()
+        .position(|m| *m == modifier)
+        .unwrap_or(0);
+    1 << idx
+}
+
+impl Style {
+    pub fn new() -> Self {
+        Style::default()
+    }
+
+    pub fn fg(mut self, color: Color) -> Self {
+        self.fg = Some(color);
+        self
+    }
+
+    pub fn bg(mut self, color: Color) -> Self {
+        self.bg = Some(color);
+        self
+    }
+
+    pub fn modifier(mut self, modifier: Modifier) -> Self {
+        self.modifiers |= modifier_bit(modifier);
+        self
+    }
+
+    pub fn remove(mut self, modifier: Modifier) -> Self {
+        self.modifiers &= !modifier_bit(modifier);
+        self
+    }
+
+    pub fn has(&self, modifier: Modifier) -> bool {
+        self.modifiers & modifier_bit(modifier) != 0
+    }
+
+    pub fn bold(self) -> Self {
+        self.modifier(Modifier::Bold)
+    }
+
+    pub fn dim(self) -> Self {
+        self.modifier(Modifier::Dim)
+    }
+
+    pub fn italic(self) -> Self {
+        self.modifier(Modifier::Italic)
+    }
+
+    pub fn underline(self) -> Self {
+        self.modifier(Modifier::Underline)
+    }
+
+    pub fn inverse(self) -> Self {
+        self.modifier(Modifier::Inverse)
+    }
+
+    pub fn strikethrough(self) -> Self {
+        self.modifier(Modifier::Striketrough)
+    }
+
+    /// `other` on top of this style, its colors win when set
+    pub fn patch(self, other: Style) -> Self {
+        Style {
+            fg: other.fg.or(self.fg),
+            bg: other.bg.or(self.bg),
+            modifiers: self.modifiers | other.modifiers,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.fg.is_none() && self.bg.is_none() && self.modifiers == 0
+    }
+
+    pub fn modifiers(&self) -> impl Iterator<Item = Modifier> + '_ {
+        Modifier::VARIANTS
+            .iter()
+            .copied()
+            .filter(move |m| self.has(*m))
+    }
+
+    /// SGR parameters that open and close the style
+    pub fn get_params(&self) -> (String, String) {
+        let mut open: Vec<String> = vec![];
+        let mut close: Vec<String> = vec![];
+
+        for modifier in self.modifiers() {
+            let (o, c) = modifier.get_params();
+            open.push(o);
+            if !close.contains(&c) {
+                close.push(c);
+            }
+        }
+        if let Some(fg) = self.fg {
+            let (o, c) = fg.get_params();
+            open.push(o);
+            close.push(c);
+        }
+        if let Some(bg) = self.bg {
+            let (o, c) = bg.bg().get_params();
+            open.push(o);
+            close.push(c);
+        }
+
+        (open.join(";"), close.join(";"))
+    }
+
+    /// Colors replaced with the nearest ones the terminal can show
+    pub fn for_level(&self, level: ColorLevel) -> Style {
+        Style {
+            fg: self.fg.map(|c| c.for_level(level)),
+            bg: self.bg.map(|c| c.for_level(level)),
+            modifiers: self.modifiers,
+        }
+    }
+
+    /// Empty when colors are disabled
+    pub fn get_open(&self) -> String {
+        let level = color_level();
+        if self.is_empty() || !level.is_enabled() {
+            return String::new();
+        }
+
+        format!("\x1b[{}m", self.for_level(level).get_params().0)
+    }
+
+    /// Empty when colors are disabled
+    pub fn get_close(&self) -> String {
+        let level = color_level();
+        if self.is_empty() || !level.is_enabled() {
+            return String::new();
+        }
+
+        format!("\x1b[{}m", self.for_level(level).get_params().1)
+    }
+
+    /// Whether an SGR with `params` turns off something this style sets
+    fn is_reset_by(&self, params: &str) -> bool {
+        let (_, close) = self.get_params();
+        let closes: Vec<&str> = close.split(';').collect();
+        let mut params = params.split(';');
+
+        while let Some(param) = params.next() {
+            match param {
+                "" | "0" => return true,
+                // Extended colors carry numbers that are not codes
+                "38" | "48" => {
+                    let skip = match params.next() {
+                        Some("5") => 1,
+                        Some("2") => 3,
+                        _ => 0,
+                    };
+                    for _ in 0..skip {
+                        params.next();
+                    }
+                }
+                param if closes.contains(&param) => return true,
+                _ => {}
+            }
+        }
+
+        false
+    }
+
+    pub fn apply_color<S: Into<String>>(&self, text: S) -> String {
+        let text = text.into();
+        if self.is_empty() || !color_level().is_enabled() {
+            return text;
+        }
+
+        let open = self.get_open();
+        let mut out = String::with_capacity(text.len() + open.len() * 2);
+        out.push_str(&open);
+
+        // Reopen after any inner sequence that would end this style early
+        let mut rest = text.as_str();
+        while let Some(start) = rest.find("\x1b[") {
+            let end = match rest[start + 2..].find(|ch: char| !ch.is_ascii_digit() && ch != ';') {
+                Some(end) => start + 2 + end,
+                None => break,
+            };
+
+            out.push_str(&rest[..=end]);
+            if rest[end..].starts_with('m') && self.is_reset_by(&rest[start + 2..end]) {
+                out.push_str(&open);
+            }
+            rest = &rest[end + 1..];
+        }
+
+        out.push_str(rest);
+        out.push_str(&self.get_close());
+        out
+    }
+
+    /// Apply style - shothand
+    pub fn a<S: Into<String>>(&self, text: S) -> String {
+        self.apply_color(text)
+    }
+}
+
+impl From<Color> for Style {
+    fn from(color: Color) -> Self {
+        Style::new().fg(color)
+    }
+}
+
+impl From<Modifier> for Style {
+    fn from(modifier: Modifier) -> Self {
+        Style::new().modifier(modifier)
+    }
+}
+
+#[derive(Debug)]
+pub struct StyleParseErr(pub String);
+
+impl Display for StyleParseErr {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Unknown style word `{}`", self.0)
+    }
+}
+
+impl FromStr for Style {
+    type Err = StyleParseErr;
+
+    /// Read words such as `bold cyan`, `dim italic #888888` or
+    /// `white on red`. A color after `on` is the background.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut style = Style::new();
+        let mut words = s.split_whitespace();
+
+        while let Some(word) = words.next() {
+            if word.eq_ignore_ascii_case("on") {
+                let color = words.next().unwrap_or_default();
+                style.bg = Some(color.parse().map_err(|_| StyleParseErr(color.into()))?);
+                continue;
+            }
+
+            let modifier = Modifier::VARIANTS.iter().find(|m| {
+                m.name().eq_ignore_ascii_case(word)
+                    || (**m == Modifier::Striketrough && word.eq_ignore_ascii_case("strikethrough"))
+            });
+            style = match modifier {
+                Some(modifier) => style.modifier(*modifier),
+                None if word.eq_ignore_ascii_case("none") => style,
+                None => style.fg(word.parse().map_err(|_| StyleParseErr(word.into()))?),
+            };
+        }
+
+        Ok(style)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Style {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let text = String::deserialize(deserializer)?;
+        text.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+impl Display for Style {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        self.get_open().fmt(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::with_color_level;
+
+    #[test]
+    pub fn combined() {
+        let style = Style::new()
+            .bold()
+            .dim()
+            .fg(Color::Red)
+            .bg(Color::Ansi256(17));
+        assert_eq!(
+            style.for_level(ColorLevel::TrueColor).get_params(),
+            (String::from("1;2;31;48;5;17"), String::from("22;39;49"))
+        );
+        assert_eq!(Style::new().a("plain"), "plain");
+    }
+
+    #[test]
+    pub fn parse() {
+        let style: Style = "bold Cyan on #202020".parse().unwrap();
+        assert_eq!(
+            style,
+            Style::new()
+                .bold()
+                .fg(Color::Cyan)
+                .bg(Color::Rgb(32, 32, 32))
+        );
+        assert_eq!("none".parse::<Style>().ok(), Some(Style::new()));
+        assert!("bold sparkly".parse::<Style>().is_err());
+    }
+
+    #[test]
+    pub fn nesting() {
+        with_color_level(ColorLevel::TrueColor, || {
+            let outer = Style::new().fg(Color::Green);
+            let inner = Color::Red.a("b");
+            assert_eq!(
+                outer.a(format!("a{}c", inner)),
+                "\x1b[32ma\x1b[31mb\x1b[39m\x1b[32mc\x1b[39m"
+            );
+
+            // Index 39 of the palette is not a close code
+            let inner = Color::Ansi256(39).bg().a("b");
+            assert_eq!(outer.a(inner), "\x1b[32m\x1b[48;5;39mb\x1b[49m\x1b[39m");
+        });
+    }
+}
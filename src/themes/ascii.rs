@@ -0,0 +1,110 @@
+use termion::clear::CurrentLine;
+use termion::cursor::Left;
+
+use super::{FormatTheme, Glyphs};
+use crate::{Color, Modifier, PromptBase, Style};
+
+/// `DefaultTheme` drawn with ASCII only, for terminals and fonts without the
+/// `rusky-icons` glyphs
+#[derive(Clone, Debug, PartialEq)]
+pub struct AsciiTheme;
+
+impl FormatTheme for AsciiTheme {
+    fn format_prompt(&self, prompt: &PromptBase) -> String {
+        let mut out = format!(
+            "{}{}{} {} {} ",
+            Left(999),
+            CurrentLine,
+            Style::new().bold().fg(Color::GreenBright).a(&prompt.prefix),
+            Modifier::Bold.a(&prompt.text),
+            Modifier::Dim.a(">")
+        );
+
+        if let Some(extra) = &prompt.extra {
+            out = format!("{}{} ", out, Color::BlackBright.a(format!("({})", extra)));
+        }
+
+        out
+    }
+
+    fn format_answered(&self, prompt: &PromptBase, answer: &str) -> String {
+        format!(
+            "{} {} {}",
+            Color::Green.a("v"),
+            Modifier::Dim.a(&prompt.text),
+            Color::Cyan.a(answer)
+        )
+    }
+
+    fn format_list_item(&self, item: &str, selected: bool) -> String {
+        if selected {
+            format!("{} {}", Color::Cyan.a(">"), item)
+        } else {
+            format!("  {}", item)
+        }
+    }
+
+    fn glyphs(&self) -> Glyphs {
+        Glyphs::ascii()
+    }
+
+    fn format_slider(&self, width: usize, handles: &[usize], active: usize) -> String {
+        let first = if handles.len() > 1 { handles[0] } else { 0 };
+        let last = handles.last().copied().unwrap_or(0);
+
+        (0..width)
+            .map(|cell| match handles.iter().position(|h| *h == cell) {
+                Some(i) if i == active => Style::new().bold().fg(Color::CyanBright).a("O"),
+                Some(_) => Color::White.a("O"),
+                None if cell >= first && cell <= last => Color::Cyan.a("="),
+                None => Modifier::Dim.a("-"),
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{PromptType, TableColumn, TablePrompt, TermRead, TreeNode, TreePrompt};
+
+    #[test]
+    pub fn ascii_only() {
+        let mut out = Vec::new();
+
+        let mut nodes = TreeNode::from_paths(&["src/main.rs", "src/lib.rs", "docs"], '/');
+        nodes[0].expanded = true;
+        let mut tree_base = PromptBase::test(PromptType::Tree);
+        let mut tree = TreePrompt {
+            base: &mut tree_base,
+            theme: &AsciiTheme,
+            nodes,
+            multi: false,
+            page_size: 0,
+        };
+        let mut stdin = (&b"\x1b[B\r"[..]).events();
+        assert_eq!(
+            tree.prompt(&mut stdin, &mut out, Some(false)).unwrap(),
+            [[0, 0]]
+        );
+
+        let mut table_base = PromptBase::test(PromptType::Table);
+        let mut table = TablePrompt {
+            base: &mut table_base,
+            theme: &AsciiTheme,
+            columns: vec![
+                TableColumn::new("Name", PromptType::String),
+                TableColumn::new("Port", PromptType::UInt),
+            ],
+            rows: vec![vec![String::from("web"), String::from("80")]],
+            page_size: 0,
+        };
+        let mut stdin = (&b"\x04"[..]).events();
+        table.prompt(&mut stdin, &mut out, Some(false)).unwrap();
+
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.contains("|- ") && out.contains("`- "));
+        assert!(out.contains("Name | Port"));
+        assert!(out.is_ascii());
+    }
+}
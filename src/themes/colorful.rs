@@ -0,0 +1,85 @@
+use termion::clear::CurrentLine;
+use termion::cursor::Left;
+
+use super::FormatTheme;
+use crate::{Color, Modifier, PromptBase, Style};
+
+/// Bright look in the style of Inquirer.js
+#[derive(Clone, Debug, PartialEq)]
+pub struct ColorfulTheme;
+
+impl FormatTheme for ColorfulTheme {
+    fn format_prompt(&self, prompt: &PromptBase) -> String {
+        let mut out = format!(
+            "{}{}{} {} ",
+            Left(999),
+            CurrentLine,
+            Style::new().bold().fg(Color::Green).a(&prompt.prefix),
+            Modifier::Bold.a(&prompt.text)
+        );
+
+        if let Some(extra) = &prompt.extra {
+            out = format!("{}{} ", out, Color::Magenta.a(format!("({})", extra)));
+        }
+
+        out
+    }
+
+    fn format_default(&self, prompt: &PromptBase) -> String {
+        match &prompt.default {
+            Some(default) => format!("{} ", Color::BlackBright.a(format!("({})", default))),
+            None => String::new(),
+        }
+    }
+
+    fn format_hint(&self, hint: &str) -> String {
+        Color::BlackBright.a(hint)
+    }
+
+    fn format_error(&self, msg: &str) -> String {
+        format!("{} {}", Color::Red.a(">>"), msg)
+    }
+
+    fn format_answered(&self, prompt: &PromptBase, answer: &str) -> String {
+        format!(
+            "{} {} {}",
+            Style::new().bold().fg(Color::Green).a("✔"),
+            Modifier::Bold.a(&prompt.text),
+            Color::Cyan.a(answer)
+        )
+    }
+
+    fn format_cancelled(&self, prompt: &PromptBase) -> String {
+        format!(
+            "{}{}",
+            self.format_prompt(prompt),
+            Color::Yellow.a("cancelled")
+        )
+    }
+
+    fn format_list_item(&self, item: &str, selected: bool) -> String {
+        if selected {
+            Color::Cyan.a(format!("❯ {}", item))
+        } else {
+            format!("  {}", item)
+        }
+    }
+
+    fn format_highlight(&self, text: &str) -> String {
+        Style::new().bold().fg(Color::White).bg(Color::Blue).a(text)
+    }
+
+    fn format_slider(&self, width: usize, handles: &[usize], active: usize) -> String {
+        let first = if handles.len() > 1 { handles[0] } else { 0 };
+        let last = handles.last().copied().unwrap_or(0);
+
+        (0..width)
+            .map(|cell| match handles.iter().position(|h| *h == cell) {
+                Some(i) if i == active => Style::new().bold().fg(Color::MagentaBright).a("●"),
+                Some(_) => Color::Magenta.a("●"),
+                None if cell >= first && cell <= last => Color::Cyan.a("━"),
+                None => Color::BlackBright.a("─"),
+            })
+            .collect()
+    }
+}
@@ -4,7 +4,78 @@ use rusky_icons::icon_sm_right_arrow;
 use termion::clear::CurrentLine;
 use termion::cursor::Left;
 
-use crate::{Color, Modifier, PromptBase};
+use crate::{CheckState, Color, Modifier, PromptBase, Style};
+
+/// Symbols the prompts draw with, see `FormatTheme::glyphs`
+#[derive(Clone, Debug, PartialEq)]
+pub struct Glyphs {
+    /// Tree guide before a node with siblings below it
+    pub tree_branch: &'static str,
+    /// Tree guide before the last node of a level
+    pub tree_last: &'static str,
+    /// Tree guide under a node with siblings below it
+    pub tree_pipe: &'static str,
+    /// Tree guide under the last node of a level
+    pub tree_empty: &'static str,
+    pub expanded: &'static str,
+    pub collapsed: &'static str,
+    /// Hides each character of a secret
+    pub secret: &'static str,
+    /// Before each entry of a list
+    pub bullet: &'static str,
+    /// One cell wide line between columns and before a text gutter
+    pub column: &'static str,
+    /// One cell wide line under a table header
+    pub rule: &'static str,
+    /// Where `column` crosses `rule`
+    pub cross: &'static str,
+    /// Keys named in hints
+    pub up_down: &'static str,
+    pub left_right: &'static str,
+}
+
+/// `rusky-icons` only has the arrow used for `collapsed`, it has no box
+/// drawing characters, so the tree guides and table lines are the Unicode
+/// ones every terminal font covers
+impl Default for Glyphs {
+    fn default() -> Self {
+        Glyphs {
+            tree_branch: "├─ ",
+            tree_last: "└─ ",
+            tree_pipe: "│  ",
+            tree_empty: "   ",
+            expanded: "▾",
+            collapsed: icon_sm_right_arrow::STR,
+            secret: "•",
+            bullet: "•",
+            column: "│",
+            rule: "─",
+            cross: "┼",
+            up_down: "↑/↓",
+            left_right: "←/→",
+        }
+    }
+}
+
+impl Glyphs {
+    pub fn ascii() -> Self {
+        Glyphs {
+            tree_branch: "|- ",
+            tree_last: "`- ",
+            tree_pipe: "|  ",
+            tree_empty: "   ",
+            expanded: "v",
+            collapsed: ">",
+            secret: "*",
+            bullet: "-",
+            column: "|",
+            rule: "-",
+            cross: "+",
+            up_down: "up/down",
+            left_right: "left/right",
+        }
+    }
+}
 
 pub trait FormatTheme: Debug {
     fn format_prompt(&self, prompt: &PromptBase) -> String {
@@ -12,7 +83,7 @@ pub trait FormatTheme: Debug {
             "{}{}{} {} {} ",
             Left(999),
             CurrentLine,
-            Modifier::Bold.a(Color::GreenBright.a(&prompt.prefix)),
+            Style::new().bold().fg(Color::GreenBright).a(&prompt.prefix),
             Modifier::Bold.a(&prompt.text),
             Modifier::Dim.a(icon_sm_right_arrow::STR)
         );
@@ -44,4 +115,111 @@ pub trait FormatTheme: Debug {
             Modifier::Dim.get_close()
         )
     }
+
+    /// Text the user typed
+    fn format_input(&self, input: &str) -> String {
+        Color::Cyan.a(input)
+    }
+
+    /// Hint shown in place of the input while it is empty
+    fn format_placeholder(&self, placeholder: &str) -> String {
+        Style::new().dim().italic().a(placeholder)
+    }
+
+    /// Secondary text such as key hints, separators and tree guides
+    fn format_hint(&self, hint: &str) -> String {
+        Modifier::Dim.a(hint)
+    }
+
+    /// Message shown when the input is invalid
+    fn format_error(&self, msg: &str) -> String {
+        Color::Red.a(msg)
+    }
+
+    /// Line left behind once the prompt is answered, see `FinishMode`
+    fn format_answered(&self, prompt: &PromptBase, answer: &str) -> String {
+        format!(
+            "{} {} {}",
+            Color::Green.a("✔"),
+            Modifier::Dim.a(&prompt.text),
+            Color::Cyan.a(answer)
+        )
+    }
+
+    /// Line left behind when the prompt is cancelled
+    fn format_cancelled(&self, prompt: &PromptBase) -> String {
+        format!(
+            "{}{}",
+            self.format_prompt(prompt),
+            Color::Red.a("<cancelled>")
+        )
+    }
+
+    /// Row of a list, `selected` is the row under the cursor
+    fn format_list_item(&self, item: &str, selected: bool) -> String {
+        if selected {
+            format!("{} {}", Color::Cyan.a(icon_sm_right_arrow::STR), item)
+        } else {
+            format!("  {}", item)
+        }
+    }
+
+    /// Item being acted on, such as a held row or the current cell
+    fn format_highlight(&self, text: &str) -> String {
+        Style::new().inverse().fg(Color::Cyan).a(text)
+    }
+
+    /// Heading inside a prompt, such as the month of a calendar
+    fn format_title(&self, title: &str) -> String {
+        Modifier::Bold.a(title)
+    }
+
+    /// Column titles of a table
+    fn format_header(&self, header: &str) -> String {
+        Modifier::Bold.a(header)
+    }
+
+    /// Name a value is shown under, such as the key of an entry
+    fn format_label(&self, label: &str) -> String {
+        Modifier::Bold.a(label)
+    }
+
+    /// Mark pointing at the choice under the cursor, such as the brackets
+    /// around the current swatch
+    fn format_pointer(&self, pointer: &str) -> String {
+        Modifier::Bold.a(pointer)
+    }
+
+    fn format_checkbox(&self, state: CheckState) -> String {
+        match state {
+            CheckState::Checked => Color::Green.a("[x]"),
+            CheckState::Partial => Color::Yellow.a("[-]"),
+            CheckState::Unchecked => String::from("[ ]"),
+        }
+    }
+
+    fn glyphs(&self) -> Glyphs {
+        Glyphs::default()
+    }
+
+    /// Bar of a slider `width` cells long. `handles` are the cells holding
+    /// a handle, the one at `active` is the one being moved.
+    fn format_slider(&self, width: usize, handles: &[usize], active: usize) -> String {
+        let first = if handles.len() > 1 { handles[0] } else { 0 };
+        let last = handles.last().copied().unwrap_or(0);
+        let mut out = String::new();
+
+        for cell in 0..width {
+            match handles.iter().position(|h| *h == cell) {
+                Some(i) if i == active => {
+                    out.push_str(&Style::new().bold().fg(Color::CyanBright).a("●"));
+                }
+                Some(_) => out.push_str(&Color::White.a("●")),
+                None if cell >= first && cell <= last => out.push_str(&Color::Cyan.a("━")),
+                None => out.push_str(&Modifier::Dim.a("─")),
+            }
+        }
+
+        out
+    }
 }
@@ -0,0 +1,167 @@
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use rusky_icons::icon_sm_right_arrow;
+use serde::Deserialize;
+use termion::clear::CurrentLine;
+use termion::cursor::Left;
+
+use super::FormatTheme;
+use crate::{Color, PromptBase, Style};
+
+#[derive(Debug)]
+pub enum ThemeError {
+    IO(io::Error),
+    Parse(String),
+}
+
+impl Display for ThemeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ThemeError::IO(err) => err.fmt(f),
+            ThemeError::Parse(msg) => msg.fmt(f),
+        }
+    }
+}
+
+/// Theme read from a TOML or JSON file. Styles are written as words, e.g.
+/// `prefix = "bold greenbright"` or `error = "white on red"`, and every
+/// missing key keeps the `DefaultTheme` look.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct ConfigTheme {
+    pub prefix: Style,
+    pub text: Style,
+    pub arrow: String,
+    pub arrow_style: Style,
+    /// `{}` is replaced with the extra hint
+    pub extra_format: String,
+    pub extra_style: Style,
+    pub default_brackets: [String; 2],
+    pub default_style: Style,
+    pub error: Style,
+    pub input: Style,
+    /// Icon starting the line of an answered prompt
+    pub check: String,
+    pub check_style: Style,
+    /// Question on the line of an answered prompt
+    pub answered_text: Style,
+    pub answer: Style,
+}
+
+impl Default for ConfigTheme {
+    fn default() -> Self {
+        ConfigTheme {
+            prefix: Style::new().bold().fg(Color::GreenBright),
+            text: Style::new().bold(),
+            arrow: String::from(icon_sm_right_arrow::STR),
+            arrow_style: Style::new().dim(),
+            extra_format: String::from("({})"),
+            extra_style: Style::new().fg(Color::BlackBright),
+            default_brackets: [String::from("["), String::from("]")],
+            default_style: Style::new().dim(),
+            error: Style::new().fg(Color::Red),
+            input: Style::new().fg(Color::Cyan),
+            check: String::from("✔"),
+            check_style: Style::new().fg(Color::Green),
+            answered_text: Style::new().dim(),
+            answer: Style::new().fg(Color::Cyan),
+        }
+    }
+}
+
+impl ConfigTheme {
+    pub fn from_toml(text: &str) -> Result<Self, ThemeError> {
+        toml::from_str(text).map_err(|err| ThemeError::Parse(err.to_string()))
+    }
+
+    pub fn from_json(text: &str) -> Result<Self, ThemeError> {
+        serde_json::from_str(text).map_err(|err| ThemeError::Parse(err.to_string()))
+    }
+
+    /// Read a `.json` file as JSON and anything else as TOML
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, ThemeError> {
+        let path = path.as_ref();
+        let text = fs::read_to_string(path).map_err(ThemeError::IO)?;
+
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some(ext) if ext.eq_ignore_ascii_case("json") => ConfigTheme::from_json(&text),
+            _ => ConfigTheme::from_toml(&text),
+        }
+    }
+}
+
+impl FormatTheme for ConfigTheme {
+    fn format_prompt(&self, prompt: &PromptBase) -> String {
+        let mut out = format!(
+            "{}{}{} {} {} ",
+            Left(999),
+            CurrentLine,
+            self.prefix.a(&prompt.prefix),
+            self.text.a(&prompt.text),
+            self.arrow_style.a(&self.arrow)
+        );
+
+        if let Some(extra) = &prompt.extra {
+            let extra = self.extra_format.replace("{}", extra);
+            out = format!("{}{} ", out, self.extra_style.a(extra));
+        }
+
+        out
+    }
+
+    fn format_default(&self, prompt: &PromptBase) -> String {
+        let default = match &prompt.default {
+            Some(d) => d,
+            None => return String::new(),
+        };
+
+        let [open, close] = &self.default_brackets;
+        format!(
+            "{} ",
+            self.default_style
+                .a(format!("{}{}{}", open, default, close))
+        )
+    }
+
+    fn format_input(&self, input: &str) -> String {
+        self.input.a(input)
+    }
+
+    fn format_error(&self, msg: &str) -> String {
+        self.error.a(msg)
+    }
+
+    fn format_answered(&self, prompt: &PromptBase, answer: &str) -> String {
+        format!(
+            "{} {} {}",
+            self.check_style.a(&self.check),
+            self.answered_text.a(&prompt.text),
+            self.answer.a(answer)
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn load() {
+        let toml = ConfigTheme::from_toml(
+            "prefix = \"bold magenta\"\narrow = \">\"\ndefault_brackets = [\"<\", \">\"]",
+        )
+        .unwrap();
+        let json = ConfigTheme::from_json(
+            r#"{"prefix": "bold magenta", "arrow": ">", "default_brackets": ["<", ">"]}"#,
+        )
+        .unwrap();
+
+        assert_eq!(toml, json);
+        assert_eq!(toml.prefix, Style::new().bold().fg(Color::Magenta));
+        assert_eq!(toml.input, ConfigTheme::default().input);
+        assert!(ConfigTheme::from_toml("error = \"sparkly\"").is_err());
+    }
+}
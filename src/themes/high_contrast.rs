@@ -0,0 +1,104 @@
+use termion::clear::CurrentLine;
+use termion::cursor::Left;
+
+use super::FormatTheme;
+use crate::{Color, Modifier, PromptBase, Style};
+
+/// Bold bright colors and no dim text, for low vision or washed out screens
+#[derive(Clone, Debug, PartialEq)]
+pub struct HighContrastTheme;
+
+impl FormatTheme for HighContrastTheme {
+    fn format_prompt(&self, prompt: &PromptBase) -> String {
+        let mut out = format!(
+            "{}{}{} {} > ",
+            Left(999),
+            CurrentLine,
+            Style::new()
+                .bold()
+                .fg(Color::YellowBright)
+                .a(&prompt.prefix),
+            Style::new().bold().fg(Color::WhiteBright).a(&prompt.text)
+        );
+
+        if let Some(extra) = &prompt.extra {
+            out = format!("{}{} ", out, Color::WhiteBright.a(format!("({})", extra)));
+        }
+
+        out
+    }
+
+    fn format_default(&self, prompt: &PromptBase) -> String {
+        match &prompt.default {
+            Some(default) => format!("{} ", Color::WhiteBright.a(format!("[{}]", default))),
+            None => String::new(),
+        }
+    }
+
+    fn format_input(&self, input: &str) -> String {
+        Style::new().bold().fg(Color::CyanBright).a(input)
+    }
+
+    fn format_placeholder(&self, placeholder: &str) -> String {
+        Modifier::Italic.a(placeholder)
+    }
+
+    fn format_hint(&self, hint: &str) -> String {
+        Color::WhiteBright.a(hint)
+    }
+
+    fn format_error(&self, msg: &str) -> String {
+        Style::new()
+            .bold()
+            .fg(Color::WhiteBright)
+            .bg(Color::Red)
+            .a(msg)
+    }
+
+    fn format_answered(&self, prompt: &PromptBase, answer: &str) -> String {
+        format!(
+            "{} {} {}",
+            Style::new().bold().fg(Color::GreenBright).a("✔"),
+            Color::WhiteBright.a(&prompt.text),
+            self.format_input(answer)
+        )
+    }
+
+    fn format_cancelled(&self, prompt: &PromptBase) -> String {
+        format!(
+            "{}{}",
+            self.format_prompt(prompt),
+            self.format_error("cancelled")
+        )
+    }
+
+    fn format_list_item(&self, item: &str, selected: bool) -> String {
+        if selected {
+            format!(
+                "{} {}",
+                Style::new().bold().fg(Color::YellowBright).a(">"),
+                Style::new().bold().inverse().a(item)
+            )
+        } else {
+            format!("  {}", item)
+        }
+    }
+
+    fn format_highlight(&self, text: &str) -> String {
+        Style::new().bold().inverse().a(text)
+    }
+
+    fn format_slider(&self, width: usize, handles: &[usize], active: usize) -> String {
+        let first = if handles.len() > 1 { handles[0] } else { 0 };
+        let last = handles.last().copied().unwrap_or(0);
+
+        (0..width)
+            .map(|cell| match handles.iter().position(|h| *h == cell) {
+                Some(i) if i == active => Style::new().bold().fg(Color::YellowBright).a("█"),
+                Some(_) => Color::WhiteBright.a("█"),
+                None if cell >= first && cell <= last => Color::CyanBright.a("━"),
+                None => Color::White.a("─"),
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,69 @@
+use termion::clear::CurrentLine;
+use termion::cursor::Left;
+
+use super::{FormatTheme, Glyphs};
+use crate::{Color, Modifier, PromptBase};
+
+/// Plain look without icons, color is kept for errors only
+#[derive(Clone, Debug, PartialEq)]
+pub struct MinimalTheme;
+
+impl FormatTheme for MinimalTheme {
+    fn format_prompt(&self, prompt: &PromptBase) -> String {
+        let mut out = format!(
+            "{}{}{} {}: ",
+            Left(999),
+            CurrentLine,
+            prompt.prefix,
+            Modifier::Bold.a(&prompt.text)
+        );
+
+        if let Some(extra) = &prompt.extra {
+            out = format!("{}{} ", out, Modifier::Dim.a(format!("({})", extra)));
+        }
+
+        out
+    }
+
+    fn format_input(&self, input: &str) -> String {
+        String::from(input)
+    }
+
+    fn format_answered(&self, prompt: &PromptBase, answer: &str) -> String {
+        format!("{}: {}", Modifier::Dim.a(&prompt.text), answer)
+    }
+
+    fn format_cancelled(&self, prompt: &PromptBase) -> String {
+        format!(
+            "{}{}",
+            self.format_prompt(prompt),
+            Color::Red.a("cancelled")
+        )
+    }
+
+    fn format_list_item(&self, item: &str, selected: bool) -> String {
+        if selected {
+            format!("> {}", Modifier::Bold.a(item))
+        } else {
+            format!("  {}", item)
+        }
+    }
+
+    fn format_highlight(&self, text: &str) -> String {
+        Modifier::Inverse.a(text)
+    }
+
+    fn glyphs(&self) -> Glyphs {
+        Glyphs::ascii()
+    }
+
+    fn format_slider(&self, width: usize, handles: &[usize], active: usize) -> String {
+        (0..width)
+            .map(|cell| match handles.iter().position(|h| *h == cell) {
+                Some(i) if i == active => Modifier::Bold.a("O"),
+                Some(_) => String::from("o"),
+                None => String::from("-"),
+            })
+            .collect()
+    }
+}
@@ -1,5 +1,19 @@
+mod ascii;
+mod colorful;
 mod common;
+#[cfg(feature = "serde")]
+mod config;
 mod default;
+mod high_contrast;
+mod minimal;
+mod named;
 
+pub use ascii::*;
+pub use colorful::*;
 pub use common::*;
+#[cfg(feature = "serde")]
+pub use config::*;
 pub use default::*;
+pub use high_contrast::*;
+pub use minimal::*;
+pub use named::*;
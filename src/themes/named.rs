@@ -0,0 +1,185 @@
+use std::env;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use super::{
+    AsciiTheme, ColorfulTheme, DefaultTheme, FormatTheme, Glyphs, HighContrastTheme, MinimalTheme,
+};
+use crate::{CheckState, PromptBase};
+
+/// One of the built-in themes, picked by name at runtime
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum NamedTheme {
+    Default,
+    Minimal,
+    Ascii,
+    Colorful,
+    HighContrast,
+}
+
+impl NamedTheme {
+    pub const VARIANTS: [NamedTheme; 5] = [
+        NamedTheme::Default,
+        NamedTheme::Minimal,
+        NamedTheme::Ascii,
+        NamedTheme::Colorful,
+        NamedTheme::HighContrast,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            NamedTheme::Default => "default",
+            NamedTheme::Minimal => "minimal",
+            NamedTheme::Ascii => "ascii",
+            NamedTheme::Colorful => "colorful",
+            NamedTheme::HighContrast => "high-contrast",
+        }
+    }
+
+    /// Theme named by the variable `var`, e.g. `RUSKY_THEME=ascii`. Falls
+    /// back to `NamedTheme::Default` when it is unset or unknown.
+    pub fn from_env(var: &str) -> NamedTheme {
+        env::var(var)
+            .ok()
+            .and_then(|name| name.parse().ok())
+            .unwrap_or(NamedTheme::Default)
+    }
+}
+
+#[derive(Debug)]
+pub struct ThemeNameErr(pub String);
+
+impl Display for ThemeNameErr {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let names: Vec<&str> = NamedTheme::VARIANTS.iter().map(|t| t.name()).collect();
+        write!(
+            f,
+            "Unknown theme `{}`, expected one of {}",
+            self.0,
+            names.join(", ")
+        )
+    }
+}
+
+impl FromStr for NamedTheme {
+    type Err = ThemeNameErr;
+
+    /// Case is ignored and `_` is read as `-`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let name = s.trim().to_lowercase().replace('_', "-");
+        NamedTheme::VARIANTS
+            .iter()
+            .find(|theme| theme.name() == name)
+            .copied()
+            .ok_or_else(|| ThemeNameErr(String::from(s)))
+    }
+}
+
+impl Display for NamedTheme {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+macro_rules! delegate {
+    ($self:ident.$method:ident($($arg:expr),*)) => {
+        match $self {
+            NamedTheme::Default => DefaultTheme.$method($($arg),*),
+            NamedTheme::Minimal => MinimalTheme.$method($($arg),*),
+            NamedTheme::Ascii => AsciiTheme.$method($($arg),*),
+            NamedTheme::Colorful => ColorfulTheme.$method($($arg),*),
+            NamedTheme::HighContrast => HighContrastTheme.$method($($arg),*),
+        }
+    };
+}
+
+impl FormatTheme for NamedTheme {
+    fn format_prompt(&self, prompt: &PromptBase) -> String {
+        delegate!(self.format_prompt(prompt))
+    }
+
+    fn format_default(&self, prompt: &PromptBase) -> String {
+        delegate!(self.format_default(prompt))
+    }
+
+    fn format_input(&self, input: &str) -> String {
+        delegate!(self.format_input(input))
+    }
+
+    fn format_placeholder(&self, placeholder: &str) -> String {
+        delegate!(self.format_placeholder(placeholder))
+    }
+
+    fn format_hint(&self, hint: &str) -> String {
+        delegate!(self.format_hint(hint))
+    }
+
+    fn format_error(&self, msg: &str) -> String {
+        delegate!(self.format_error(msg))
+    }
+
+    fn format_answered(&self, prompt: &PromptBase, answer: &str) -> String {
+        delegate!(self.format_answered(prompt, answer))
+    }
+
+    fn format_cancelled(&self, prompt: &PromptBase) -> String {
+        delegate!(self.format_cancelled(prompt))
+    }
+
+    fn format_list_item(&self, item: &str, selected: bool) -> String {
+        delegate!(self.format_list_item(item, selected))
+    }
+
+    fn format_highlight(&self, text: &str) -> String {
+        delegate!(self.format_highlight(text))
+    }
+
+    fn format_title(&self, title: &str) -> String {
+        delegate!(self.format_title(title))
+    }
+
+    fn format_header(&self, header: &str) -> String {
+        delegate!(self.format_header(header))
+    }
+
+    fn format_label(&self, label: &str) -> String {
+        delegate!(self.format_label(label))
+    }
+
+    fn format_pointer(&self, pointer: &str) -> String {
+        delegate!(self.format_pointer(pointer))
+    }
+
+    fn format_checkbox(&self, state: CheckState) -> String {
+        delegate!(self.format_checkbox(state))
+    }
+
+    fn glyphs(&self) -> Glyphs {
+        delegate!(self.glyphs())
+    }
+
+    fn format_slider(&self, width: usize, handles: &[usize], active: usize) -> String {
+        delegate!(self.format_slider(width, handles, active))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn by_name() {
+        for theme in NamedTheme::VARIANTS {
+            assert_eq!(theme.name().parse::<NamedTheme>().ok(), Some(theme));
+        }
+        assert_eq!(
+            "High_Contrast".parse::<NamedTheme>().ok(),
+            Some(NamedTheme::HighContrast)
+        );
+        assert!("sparkly".parse::<NamedTheme>().is_err());
+        assert_eq!(
+            NamedTheme::from_env("RUSKY_TEST_UNSET_THEME"),
+            NamedTheme::Default
+        );
+    }
+}